@@ -0,0 +1,43 @@
+-- RSVP revision history
+-- rsvps keeps only the latest answer per guest, so every submission is also
+-- appended here with where it came from and what changed.
+
+CREATE TABLE IF NOT EXISTS rsvp_revisions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    rsvp_id UUID REFERENCES rsvps(id) ON DELETE CASCADE,
+    guest_id UUID NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    invite_id UUID REFERENCES invites(id) ON DELETE CASCADE,
+    attending BOOLEAN NOT NULL,
+    dietary_restrictions TEXT,
+    song_requests TEXT,
+    message TEXT,
+    source VARCHAR(20) NOT NULL DEFAULT 'guest_link', -- guest_link, admin, phone
+    changes JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rsvp_revisions_invite ON rsvp_revisions(invite_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_rsvp_revisions_guest ON rsvp_revisions(guest_id, created_at);
+
+-- One-time data steps. These files are re-applied on every boot, so each
+-- data change records a key here when it runs and is skipped after that;
+-- data an admin later edits or deletes is not put back.
+CREATE TABLE IF NOT EXISTS data_seeds (
+    key VARCHAR(100) PRIMARY KEY,
+    seeded_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Seed the timeline with the current answer for RSVPs submitted before
+-- revisions were recorded.
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('rsvp_revisions')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+INSERT INTO rsvp_revisions (rsvp_id, guest_id, invite_id, attending, dietary_restrictions, song_requests, message, created_at)
+SELECT r.id, r.guest_id, r.invite_id, r.attending, r.dietary_restrictions, r.song_requests, r.message,
+       COALESCE(r.updated_at, r.submitted_at, NOW())
+FROM rsvps r
+CROSS JOIN seed
+WHERE r.guest_id IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM rsvp_revisions rr WHERE rr.rsvp_id = r.id);
@@ -0,0 +1,33 @@
+-- Per-guest meal selection
+-- Admins manage the menu as courses with options; attending guests pick at
+-- most one option per course when they RSVP.
+
+CREATE TABLE IF NOT EXISTS menu_courses (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(100) NOT NULL,
+    required BOOLEAN NOT NULL DEFAULT false,
+    display_order INT DEFAULT 0,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS menu_options (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    course_id UUID NOT NULL REFERENCES menu_courses(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    is_kids_meal BOOLEAN NOT NULL DEFAULT false,
+    display_order INT DEFAULT 0,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS rsvp_meal_selections (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    guest_id UUID NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    course_id UUID NOT NULL REFERENCES menu_courses(id) ON DELETE CASCADE,
+    option_id UUID NOT NULL REFERENCES menu_options(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (guest_id, course_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_menu_options_course ON menu_options(course_id);
+CREATE INDEX IF NOT EXISTS idx_rsvp_meal_selections_option ON rsvp_meal_selections(option_id);
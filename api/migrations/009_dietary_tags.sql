@@ -0,0 +1,12 @@
+-- Structured dietary restrictions
+-- Standard allergen/diet codes are stored alongside the free-text notes.
+-- dietary_review_status is 'pending' when the codes were suggested from the
+-- free text and still need an admin to confirm them, 'reviewed' afterwards,
+-- and NULL when the guest picked the codes themselves.
+
+ALTER TABLE rsvps ADD COLUMN IF NOT EXISTS dietary_tags TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE rsvps ADD COLUMN IF NOT EXISTS dietary_review_status VARCHAR(20);
+
+ALTER TABLE rsvp_revisions ADD COLUMN IF NOT EXISTS dietary_tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_rsvps_dietary_tags ON rsvps USING GIN (dietary_tags);
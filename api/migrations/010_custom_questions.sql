@@ -0,0 +1,35 @@
+-- Admin-defined RSVP questions
+-- New questions no longer need a column on rsvps (like song_requests did).
+-- Guest-scoped answers have a guest_id; invite-scoped answers leave it NULL.
+
+CREATE TABLE IF NOT EXISTS rsvp_questions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    prompt TEXT NOT NULL,
+    question_type VARCHAR(20) NOT NULL, -- yes_no, single_choice, multi_choice, text, number
+    scope VARCHAR(10) NOT NULL DEFAULT 'guest', -- guest, invite
+    required BOOLEAN NOT NULL DEFAULT false,
+    choices TEXT[] NOT NULL DEFAULT '{}',
+    display_order INT DEFAULT 0,
+    active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS rsvp_answers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    question_id UUID NOT NULL REFERENCES rsvp_questions(id) ON DELETE CASCADE,
+    invite_id UUID NOT NULL REFERENCES invites(id) ON DELETE CASCADE,
+    guest_id UUID REFERENCES guests(id) ON DELETE CASCADE,
+    value JSONB NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rsvp_answers_guest
+    ON rsvp_answers (question_id, guest_id)
+    WHERE guest_id IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rsvp_answers_invite
+    ON rsvp_answers (question_id, invite_id)
+    WHERE guest_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_rsvp_answers_invite_id ON rsvp_answers(invite_id);
@@ -0,0 +1,55 @@
+-- Multi-event RSVPs
+-- Events marked invite_all are shown to every invite; other events (rehearsal
+-- dinner, brunch) are only shown to invites listed in invite_events.
+-- rsvps.attending remains the answer for the wedding itself and is used for
+-- invite_all events when a guest has no explicit event response.
+
+CREATE TABLE IF NOT EXISTS events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    location VARCHAR(255),
+    starts_at TIMESTAMPTZ,
+    ends_at TIMESTAMPTZ,
+    invite_all BOOLEAN NOT NULL DEFAULT false,
+    display_order INT DEFAULT 0,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS invite_events (
+    invite_id UUID NOT NULL REFERENCES invites(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (invite_id, event_id)
+);
+
+CREATE TABLE IF NOT EXISTS rsvp_event_responses (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    guest_id UUID NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    attending BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (guest_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invite_events_event ON invite_events(event_id);
+CREATE INDEX IF NOT EXISTS idx_rsvp_event_responses_event ON rsvp_event_responses(event_id);
+
+-- Seed the wedding day itself (Saturday, August 15, 2026 at 4pm, Rouge).
+-- Runs once under a data_seeds key (see 007_rsvp_revisions.sql).
+-- Databases that already have events were seeded before the key existed.
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('wedding_events')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+INSERT INTO events (name, location, starts_at, ends_at, invite_all, display_order)
+SELECT v.name, v.location, v.starts_at, v.ends_at, true, v.display_order
+FROM seed
+CROSS JOIN (VALUES
+    ('Ceremony', 'Rouge, 1240 8 Ave SE, Calgary, Alberta',
+     '2026-08-15 16:00:00-06'::timestamptz, '2026-08-15 17:00:00-06'::timestamptz, 1),
+    ('Reception', 'Rouge, 1240 8 Ave SE, Calgary, Alberta',
+     '2026-08-15 17:00:00-06'::timestamptz, '2026-08-15 23:00:00-06'::timestamptz, 2)
+) AS v (name, location, starts_at, ends_at, display_order)
+WHERE NOT EXISTS (SELECT 1 FROM events);
@@ -0,0 +1,7 @@
+-- RSVP confirmation emails
+-- All confirmations are recorded in email_sends against a single system
+-- campaign, created on first use like the one-month reminder campaign.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_campaigns_rsvp_confirmation
+    ON email_campaigns (template_type)
+    WHERE template_type = 'rsvp_confirmation';
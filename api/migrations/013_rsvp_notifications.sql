@@ -0,0 +1,25 @@
+-- RSVP notifications for the couple
+-- A single settings row controls who is notified and how often:
+-- 'off', 'immediate' (one email per RSVP change) or 'daily' (a digest sent
+-- once a day at digest_hour_utc). last_digest_at marks where the next digest
+-- picks up.
+
+CREATE TABLE IF NOT EXISTS notification_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    recipients TEXT[] NOT NULL DEFAULT '{}',
+    frequency VARCHAR(20) NOT NULL DEFAULT 'daily',
+    digest_hour_utc INT NOT NULL DEFAULT 14, -- 8am in Calgary (MDT)
+    last_digest_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('notification_settings')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+INSERT INTO notification_settings (id)
+SELECT true FROM seed
+ON CONFLICT DO NOTHING;
+
+CREATE INDEX IF NOT EXISTS idx_rsvp_revisions_created_at ON rsvp_revisions(created_at);
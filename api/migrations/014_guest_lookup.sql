@@ -0,0 +1,7 @@
+-- Find-my-invitation lookup
+-- Guests who lost their invite code can look it up by name plus a second
+-- factor on file: their email or their postal code.
+
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS postal_code VARCHAR(20);
+
+CREATE INDEX IF NOT EXISTS idx_guests_email_lower ON guests (LOWER(email));
@@ -0,0 +1,9 @@
+-- Admin-entered RSVPs
+-- RSVPs taken by phone, on a paper card or in person are entered by an
+-- admin. The channel is stored as the source and the admin's name as
+-- entered_by, on both the current RSVP and its revision history.
+
+ALTER TABLE rsvps ADD COLUMN IF NOT EXISTS source VARCHAR(20) NOT NULL DEFAULT 'guest_link';
+ALTER TABLE rsvps ADD COLUMN IF NOT EXISTS entered_by VARCHAR(255);
+
+ALTER TABLE rsvp_revisions ADD COLUMN IF NOT EXISTS entered_by VARCHAR(255);
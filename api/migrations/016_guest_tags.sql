@@ -0,0 +1,5 @@
+-- Guest tags for RSVP breakdowns (e.g. "college", "work", "family-friends")
+
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_guests_tags ON guests USING GIN (tags);
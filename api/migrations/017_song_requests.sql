@@ -0,0 +1,29 @@
+-- Song requests for the DJ
+-- Free-text rsvps.song_requests are parsed into songs, merged across guests
+-- by song_key. Admins approve or ban songs; the DJ reads approved songs
+-- through a share link.
+
+CREATE TABLE IF NOT EXISTS songs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    song_key TEXT UNIQUE NOT NULL,
+    artist VARCHAR(255),
+    title VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, approved, banned
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS song_request_guests (
+    song_id UUID NOT NULL REFERENCES songs(id) ON DELETE CASCADE,
+    guest_id UUID NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (song_id, guest_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_song_request_guests_guest ON song_request_guests(guest_id);
+
+CREATE TABLE IF NOT EXISTS playlist_share_links (
+    token VARCHAR(64) PRIMARY KEY,
+    revoked BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
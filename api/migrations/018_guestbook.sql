@@ -0,0 +1,23 @@
+-- Moderated guestbook
+-- Entries come from RSVP messages the guest chose to publish (rsvp_id set)
+-- or from posts made with an invite code. Nothing is public until an admin
+-- approves it; an edited RSVP message goes back to pending.
+
+CREATE TABLE IF NOT EXISTS guestbook_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    invite_id UUID REFERENCES invites(id) ON DELETE CASCADE,
+    guest_id UUID REFERENCES guests(id) ON DELETE SET NULL,
+    rsvp_id UUID REFERENCES rsvps(id) ON DELETE CASCADE,
+    author_name VARCHAR(255) NOT NULL,
+    message TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, approved, rejected
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_guestbook_entries_rsvp
+    ON guestbook_entries (rsvp_id)
+    WHERE rsvp_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_guestbook_entries_status ON guestbook_entries(status);
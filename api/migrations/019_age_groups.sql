@@ -0,0 +1,6 @@
+-- Age groups for headcount and meal planning
+-- age_group is adult, child or infant; age is optional. Children and infants
+-- do not count toward an invite's two-adult (guest plus one) limit.
+
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS age_group VARCHAR(10) NOT NULL DEFAULT 'adult';
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS age INT;
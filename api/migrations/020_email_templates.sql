@@ -0,0 +1,54 @@
+-- Editable email templates
+-- Templates are MiniJinja source. email_templates holds the current version;
+-- every save (and rollback) appends to email_template_versions. Defaults for
+-- the built-in templates are seeded by the API at startup.
+
+CREATE TABLE IF NOT EXISTS email_templates (
+    template_type VARCHAR(50) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    subject TEXT NOT NULL,
+    html TEXT NOT NULL,
+    version INT NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS email_template_versions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    template_type VARCHAR(50) NOT NULL REFERENCES email_templates(template_type) ON DELETE CASCADE,
+    version INT NOT NULL,
+    subject TEXT NOT NULL,
+    html TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (template_type, version)
+);
+
+-- Wedding details shared by every template as {{ wedding.<name> }}
+CREATE TABLE IF NOT EXISTS email_template_variables (
+    name VARCHAR(50) PRIMARY KEY,
+    value TEXT NOT NULL,
+    description TEXT,
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('email_template_variables')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+INSERT INTO email_template_variables (name, value, description)
+SELECT v.name, v.value, v.description
+FROM seed
+CROSS JOIN (VALUES
+    ('couple', 'Sam & Jonah', 'Short couple names used in greetings and sign-offs'),
+    ('full_names', 'Samantha Orr and Jonah Duckworth', 'Full names as printed on the invitation'),
+    ('date', 'Saturday, August 15, 2026', 'Wedding date with weekday'),
+    ('short_date', 'August 15, 2026', 'Wedding date without weekday'),
+    ('time', 'four o''clock in the afternoon', 'Ceremony time as written on the invitation'),
+    ('venue', 'Rouge', 'Venue name'),
+    ('venue_address', '1240 8 Ave SE, Calgary, Alberta', 'Venue street address'),
+    ('city', 'Calgary', 'Wedding city'),
+    ('region', 'Alberta', 'Wedding province'),
+    ('rsvp_deadline', 'April 30th 2026', 'RSVP deadline as shown to guests')
+) AS v (name, value, description)
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,15 @@
+-- Saved campaign audiences
+-- filters is a JSON object of optional guest filters (RSVP status, side,
+-- relationship, tags, email opens, invitation sent). A campaign with a
+-- segment is only sent to the guests that match it.
+
+CREATE TABLE IF NOT EXISTS audience_segments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(255) NOT NULL,
+    filters JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+ALTER TABLE email_campaigns
+    ADD COLUMN IF NOT EXISTS segment_id UUID REFERENCES audience_segments(id) ON DELETE SET NULL;
@@ -0,0 +1,16 @@
+-- Email suppression list
+-- Addresses are stored lowercased. Bounces, complaints and manual entries
+-- stop every email; an opt-out only stops non-essential email (campaigns
+-- and reminders), so invitations and RSVP confirmations still go out.
+
+CREATE TABLE IF NOT EXISTS email_suppressions (
+    email VARCHAR(255) PRIMARY KEY,
+    reason VARCHAR(20) NOT NULL CHECK (reason IN ('bounce', 'complaint', 'manual', 'opt_out')),
+    detail TEXT,
+    guest_id UUID REFERENCES guests(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Secret for each guest's opt-out link
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS opt_out_token UUID NOT NULL DEFAULT uuid_generate_v4();
+CREATE UNIQUE INDEX IF NOT EXISTS idx_guests_opt_out_token ON guests(opt_out_token);
@@ -0,0 +1,19 @@
+-- Every reminder gets exactly one campaign, keyed by the reminder's
+-- template type. Existing one-month reminder campaigns are carried over.
+
+ALTER TABLE email_campaigns
+    ADD COLUMN IF NOT EXISTS reminder_type VARCHAR(50);
+
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('reminder_campaigns')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+UPDATE email_campaigns
+SET reminder_type = template_type
+FROM seed
+WHERE template_type = 'one_month_reminder' AND reminder_type IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_campaigns_reminder_type
+    ON email_campaigns (reminder_type)
+    WHERE reminder_type IS NOT NULL;
@@ -0,0 +1,26 @@
+-- Delivery log: every guest email records what was sent, to whom, and the
+-- provider's message id so webhook events can update its status.
+
+ALTER TABLE email_sends
+    ADD COLUMN IF NOT EXISTS provider_message_id VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS recipients TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS subject TEXT,
+    ADD COLUMN IF NOT EXISTS template_type VARCHAR(50),
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'sent'
+        CHECK (status IN ('sent', 'delivery_delayed', 'delivered', 'bounced', 'complained')),
+    ADD COLUMN IF NOT EXISTS status_updated_at TIMESTAMPTZ;
+
+-- Earlier sends take their template from the campaign they belong to
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('email_send_templates')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+UPDATE email_sends es
+SET template_type = ec.template_type
+FROM email_campaigns ec, seed
+WHERE ec.id = es.campaign_id AND es.template_type IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_sends_provider_message_id
+    ON email_sends (provider_message_id)
+    WHERE provider_message_id IS NOT NULL;
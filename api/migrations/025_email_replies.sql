@@ -0,0 +1,26 @@
+-- Replies guests send to our emails, captured by the inbound-email webhook.
+-- Each reply is matched to a guest by the sender's address; unmatched
+-- replies are kept with no guest. provider_message_id makes retried
+-- webhooks idempotent.
+
+CREATE TABLE IF NOT EXISTS email_replies (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    guest_id UUID REFERENCES guests(id) ON DELETE SET NULL,
+    invite_id UUID REFERENCES invites(id) ON DELETE SET NULL,
+    from_email VARCHAR(255) NOT NULL,
+    from_name VARCHAR(255),
+    subject TEXT,
+    text_body TEXT,
+    html_body TEXT,
+    provider_message_id VARCHAR(255) UNIQUE,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    handled_at TIMESTAMPTZ,
+    forwarded_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_replies_guest ON email_replies(guest_id);
+CREATE INDEX IF NOT EXISTS idx_email_replies_from_email ON email_replies(LOWER(from_email));
+
+-- Forward each reply to the notification recipients as it arrives
+ALTER TABLE notification_settings
+    ADD COLUMN IF NOT EXISTS forward_replies BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,23 @@
+-- Emails the provider accepted each month, counted per recipient, so sends
+-- can be checked against the plan's monthly quota.
+
+CREATE TABLE IF NOT EXISTS email_provider_usage (
+    month DATE PRIMARY KEY,
+    email_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Start from the delivery log; sends from before it have no recipients and
+-- count once
+WITH seed AS (
+    INSERT INTO data_seeds (key) VALUES ('email_provider_usage')
+    ON CONFLICT (key) DO NOTHING
+    RETURNING key
+)
+INSERT INTO email_provider_usage (month, email_count)
+SELECT date_trunc('month', sent_at)::date, SUM(GREATEST(cardinality(recipients), 1))
+FROM email_sends
+CROSS JOIN seed
+WHERE sent_at IS NOT NULL
+GROUP BY 1
+ON CONFLICT (month) DO NOTHING;
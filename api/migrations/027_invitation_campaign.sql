@@ -0,0 +1,74 @@
+-- Invitations are recorded in email_sends against a single system campaign,
+-- like RSVP confirmations, so they get per-invite history and open tracking.
+
+-- Moving past invitations onto the system campaign runs once, under a
+-- data_seeds key (see 007_rsvp_revisions.sql), so campaigns an admin
+-- creates later are left alone on the next boot.
+DO $$
+BEGIN
+    INSERT INTO data_seeds (key) VALUES ('invitation_campaign')
+    ON CONFLICT (key) DO NOTHING;
+    IF NOT FOUND THEN
+        RETURN;
+    END IF;
+
+    -- Campaigns created by hand with the invitation template are merged
+    -- into the oldest one, so the unique index below can be built
+    WITH keep AS (
+        SELECT id FROM email_campaigns
+        WHERE template_type = 'invitation'
+        ORDER BY created_at, id
+        LIMIT 1
+    )
+    UPDATE email_sends es
+    SET campaign_id = keep.id
+    FROM email_campaigns ec, keep
+    WHERE es.campaign_id = ec.id
+      AND ec.template_type = 'invitation'
+      AND ec.id <> keep.id;
+
+    DELETE FROM email_campaigns ec
+    WHERE ec.template_type = 'invitation'
+      AND ec.id <> (
+          SELECT id FROM email_campaigns
+          WHERE template_type = 'invitation'
+          ORDER BY created_at, id
+          LIMIT 1
+      );
+
+    INSERT INTO email_campaigns (name, subject, template_type)
+    SELECT 'Wedding Invitations',
+           COALESCE((SELECT subject FROM email_templates WHERE template_type = 'invitation'), 'Wedding Invitations'),
+           'invitation'
+    WHERE NOT EXISTS (SELECT 1 FROM email_campaigns WHERE template_type = 'invitation')
+      AND (EXISTS (SELECT 1 FROM invites WHERE invite_sent_at IS NOT NULL)
+           OR EXISTS (SELECT 1 FROM email_sends WHERE template_type = 'invitation'));
+
+    -- Invitations logged before they belonged to a campaign
+    UPDATE email_sends es
+    SET campaign_id = ec.id
+    FROM email_campaigns ec
+    WHERE ec.template_type = 'invitation'
+      AND es.template_type = 'invitation'
+      AND es.campaign_id IS NULL;
+
+    -- Invitations sent before the delivery log only set invite_sent_at
+    INSERT INTO email_sends (campaign_id, invite_id, sent_at, template_type)
+    SELECT ec.id, i.id, i.invite_sent_at, 'invitation'
+    FROM invites i
+    CROSS JOIN email_campaigns ec
+    WHERE ec.template_type = 'invitation'
+      AND i.invite_sent_at IS NOT NULL
+      AND NOT EXISTS (
+          SELECT 1 FROM email_sends es WHERE es.invite_id = i.id AND es.campaign_id = ec.id
+      );
+
+    UPDATE email_campaigns ec
+    SET sent_count = (SELECT COUNT(*) FROM email_sends es WHERE es.campaign_id = ec.id),
+        sent_at = COALESCE(ec.sent_at, (SELECT MIN(es.sent_at) FROM email_sends es WHERE es.campaign_id = ec.id))
+    WHERE ec.template_type = 'invitation';
+END $$;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email_campaigns_invitation
+    ON email_campaigns (template_type)
+    WHERE template_type = 'invitation';
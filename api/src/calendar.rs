@@ -0,0 +1,241 @@
+// iCalendar (RFC 5545) files and add-to-calendar links for the wedding events
+
+use time::{Date, Duration, Month, OffsetDateTime, UtcOffset, Weekday};
+
+use crate::models::Event;
+
+pub const TIMEZONE: &str = "America/Edmonton";
+pub const ICS_FILENAME: &str = "sam-and-jonah-wedding.ics";
+const PRODID: &str = "-//Sam & Jonah//Wedding//EN";
+const UID_DOMAIN: &str = "samandjonah.com";
+/// Length given to events saved without an end time
+const DEFAULT_LENGTH: Duration = Duration::hours(1);
+
+/// Mountain time rules since 2007: daylight time from the second Sunday in
+/// March to the first Sunday in November, changing at 2:00 local time.
+const VTIMEZONE: &[&str] = &[
+    "BEGIN:VTIMEZONE",
+    "TZID:America/Edmonton",
+    "BEGIN:DAYLIGHT",
+    "TZOFFSETFROM:-0700",
+    "TZOFFSETTO:-0600",
+    "TZNAME:MDT",
+    "DTSTART:19700308T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU",
+    "END:DAYLIGHT",
+    "BEGIN:STANDARD",
+    "TZOFFSETFROM:-0600",
+    "TZOFFSETTO:-0700",
+    "TZNAME:MST",
+    "DTSTART:19701101T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+/// Calendar title for an event, e.g. "Sam & Jonah's Wedding: Ceremony".
+pub fn event_title(couple: &str, event: &Event) -> String {
+    format!("{}'s Wedding: {}", couple, event.name)
+}
+
+/// The `n`th given weekday of a month.
+fn nth_weekday(year: i32, month: Month, weekday: Weekday, n: u8) -> Date {
+    let first = Date::from_calendar_date(year, month, 1).expect("valid date");
+    let offset = (7 + weekday.number_days_from_sunday() - first.weekday().number_days_from_sunday()) % 7;
+    first + Duration::days(i64::from(offset) + 7 * i64::from(n - 1))
+}
+
+/// UTC offset in Edmonton at an instant.
+pub fn edmonton_offset(at: OffsetDateTime) -> UtcOffset {
+    let mst = UtcOffset::from_hms(-7, 0, 0).expect("valid offset");
+    let mdt = UtcOffset::from_hms(-6, 0, 0).expect("valid offset");
+    let year = at.to_offset(mst).year();
+    // 2:00 MST and 2:00 MDT, in UTC
+    let starts = nth_weekday(year, Month::March, Weekday::Sunday, 2).midnight().assume_utc() + Duration::hours(9);
+    let ends = nth_weekday(year, Month::November, Weekday::Sunday, 1).midnight().assume_utc() + Duration::hours(8);
+    if at >= starts && at < ends { mdt } else { mst }
+}
+
+pub fn to_edmonton(at: OffsetDateTime) -> OffsetDateTime {
+    at.to_offset(edmonton_offset(at))
+}
+
+fn end_time(event: &Event, starts_at: OffsetDateTime) -> OffsetDateTime {
+    event.ends_at.filter(|end| *end > starts_at).unwrap_or(starts_at + DEFAULT_LENGTH)
+}
+
+/// Local date-time in iCalendar form, e.g. 20260815T160000.
+fn local_stamp(at: OffsetDateTime) -> String {
+    let at = to_edmonton(at);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}",
+        at.year(), u8::from(at.month()), at.day(), at.hour(), at.minute(), at.second()
+    )
+}
+
+/// UTC date-time in iCalendar form, e.g. 20260815T220000Z.
+fn utc_stamp(at: OffsetDateTime) -> String {
+    let at = at.to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        at.year(), u8::from(at.month()), at.day(), at.hour(), at.minute(), at.second()
+    )
+}
+
+/// Escape a TEXT value (RFC 5545 section 3.3.11).
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line to 75 octets, continuing on lines that start with a
+/// space, without splitting a UTF-8 character.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// A calendar with every timed event. `url` is shown as the events' link.
+pub fn ics(events: &[Event], couple: &str, url: &str, now: OffsetDateTime) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&format!("{}'s Wedding", couple))),
+        format!("X-WR-TIMEZONE:{}", TIMEZONE),
+    ];
+    lines.extend(VTIMEZONE.iter().map(|l| l.to_string()));
+
+    for event in events {
+        let Some(starts_at) = event.starts_at else {
+            continue;
+        };
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@{}", event.id, UID_DOMAIN));
+        lines.push(format!("DTSTAMP:{}", utc_stamp(now)));
+        lines.push(format!("DTSTART;TZID={}:{}", TIMEZONE, local_stamp(starts_at)));
+        lines.push(format!("DTEND;TZID={}:{}", TIMEZONE, local_stamp(end_time(event, starts_at))));
+        lines.push(format!("SUMMARY:{}", escape_text(&event_title(couple, event))));
+        if let Some(location) = event.location.as_deref().filter(|l| !l.trim().is_empty()) {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = event.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if !url.is_empty() {
+            lines.push(format!("URL:{}", url));
+        }
+        lines.push("STATUS:CONFIRMED".to_string());
+        lines.push("TRANSP:OPAQUE".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        push_line(&mut out, line);
+    }
+    out
+}
+
+/// Link that opens an event in Google Calendar, or None without a start time.
+pub fn google_url(event: &Event, couple: &str) -> Option<String> {
+    let starts_at = event.starts_at?;
+    let dates = format!("{}/{}", utc_stamp(starts_at), utc_stamp(end_time(event, starts_at)));
+    let title = event_title(couple, event);
+    let params = [
+        ("action", "TEMPLATE"),
+        ("text", title.as_str()),
+        ("dates", dates.as_str()),
+        ("ctz", TIMEZONE),
+        ("location", event.location.as_deref().unwrap_or_default()),
+        ("details", event.description.as_deref().unwrap_or_default()),
+    ];
+    reqwest::Url::parse_with_params("https://calendar.google.com/calendar/render", params)
+        .ok()
+        .map(String::from)
+}
+
+/// Link that opens an event in Outlook on the web, or None without a start time.
+pub fn outlook_url(event: &Event, couple: &str) -> Option<String> {
+    let rfc3339 = time::format_description::well_known::Rfc3339;
+    let starts_at = event.starts_at?;
+    let start = to_edmonton(starts_at).format(&rfc3339).ok()?;
+    let end = to_edmonton(end_time(event, starts_at)).format(&rfc3339).ok()?;
+    let title = event_title(couple, event);
+    let params = [
+        ("path", "/calendar/action/compose"),
+        ("rru", "addevent"),
+        ("subject", title.as_str()),
+        ("startdt", start.as_str()),
+        ("enddt", end.as_str()),
+        ("location", event.location.as_deref().unwrap_or_default()),
+        ("body", event.description.as_deref().unwrap_or_default()),
+    ];
+    reqwest::Url::parse_with_params("https://outlook.live.com/calendar/0/deeplink/compose", params)
+        .ok()
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edmonton_offset, google_url, ics};
+    use crate::models::Event;
+    use time::{Duration, OffsetDateTime};
+    use uuid::Uuid;
+
+    fn ceremony() -> Event {
+        // 2026-08-15 16:00 MDT
+        let starts_at = OffsetDateTime::from_unix_timestamp(1_786_831_200).unwrap();
+        Event {
+            id: Uuid::nil(),
+            name: "Ceremony".to_string(),
+            description: Some("Please arrive by 3:45pm; seating is first come, first served.".to_string()),
+            location: Some("Rouge, 1240 8 Ave SE, Calgary, Alberta".to_string()),
+            starts_at: Some(starts_at),
+            ends_at: Some(starts_at + Duration::hours(1)),
+            invite_all: true,
+            display_order: 1,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn ics_uses_edmonton_local_time_and_escapes_text() {
+        let now = OffsetDateTime::from_unix_timestamp(1_767_225_600).unwrap();
+        let calendar = ics(&[ceremony()], "Sam & Jonah", "https://samandjonah.com", now);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.contains("TZID:America/Edmonton\r\n"));
+        assert!(calendar.contains("DTSTART;TZID=America/Edmonton:20260815T160000\r\n"));
+        assert!(calendar.contains("DTEND;TZID=America/Edmonton:20260815T170000\r\n"));
+        assert!(calendar.contains("DTSTAMP:20260101T000000Z\r\n"));
+        assert!(calendar.contains("SUMMARY:Sam & Jonah's Wedding: Ceremony\r\n"));
+        assert!(calendar.contains("LOCATION:Rouge\\, 1240 8 Ave SE\\, Calgary\\, Alberta\r\n"));
+        assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+        assert!(calendar.replace("\r\n ", "")
+            .contains("DESCRIPTION:Please arrive by 3:45pm\\; seating is first come\\, first served.\r\n"));
+
+        let winter = OffsetDateTime::from_unix_timestamp(1_767_225_600).unwrap();
+        assert_eq!(edmonton_offset(winter).whole_hours(), -7);
+        assert_eq!(edmonton_offset(ceremony().starts_at.unwrap()).whole_hours(), -6);
+
+        let google = google_url(&ceremony(), "Sam & Jonah").unwrap();
+        assert!(google.contains("dates=20260815T220000Z%2F20260815T230000Z"));
+        assert!(google.contains("ctz=America%2FEdmonton"));
+    }
+}
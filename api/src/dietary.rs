@@ -0,0 +1,85 @@
+// Standard allergen and diet list used for structured dietary answers
+
+use serde::Serialize;
+
+pub const REVIEW_PENDING: &str = "pending";
+pub const REVIEW_DONE: &str = "reviewed";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DietaryOption {
+    pub code: &'static str,
+    pub label: &'static str,
+    pub kind: &'static str, // "allergy" or "diet"
+    #[serde(skip)]
+    keywords: &'static [&'static str],
+}
+
+const fn option(
+    code: &'static str,
+    label: &'static str,
+    kind: &'static str,
+    keywords: &'static [&'static str],
+) -> DietaryOption {
+    DietaryOption { code, label, kind, keywords }
+}
+
+pub const DIETARY_OPTIONS: &[DietaryOption] = &[
+    option("gluten_free", "Gluten-free / celiac", "allergy", &["gluten", "celiac", "coeliac", "gf", "wheat"]),
+    option("nut_allergy", "Peanut or tree nut allergy", "allergy", &["nut", "nuts", "peanut", "peanuts", "tree nut", "almond", "almonds", "cashew", "cashews"]),
+    option("shellfish", "Shellfish allergy", "allergy", &["shellfish", "shrimp", "prawn", "prawns", "lobster", "crab", "scallop", "scallops"]),
+    option("fish", "Fish allergy", "allergy", &["fish allergy", "allergic to fish", "no fish"]),
+    option("dairy_free", "Dairy-free / lactose intolerant", "allergy", &["dairy", "lactose", "milk", "df"]),
+    option("egg", "Egg allergy", "allergy", &["egg", "eggs"]),
+    option("soy", "Soy allergy", "allergy", &["soy", "soya"]),
+    option("sesame", "Sesame allergy", "allergy", &["sesame"]),
+    option("vegetarian", "Vegetarian", "diet", &["vegetarian", "veggie", "no meat"]),
+    option("vegan", "Vegan", "diet", &["vegan", "plant based"]),
+    option("pescatarian", "Pescatarian", "diet", &["pescatarian", "pescetarian"]),
+    option("halal", "Halal", "diet", &["halal"]),
+    option("kosher", "Kosher", "diet", &["kosher"]),
+];
+
+pub fn find(code: &str) -> Option<&'static DietaryOption> {
+    DIETARY_OPTIONS.iter().find(|o| o.code == code)
+}
+
+pub fn label(code: &str) -> &str {
+    find(code).map(|o| o.label).unwrap_or(code)
+}
+
+/// Best-effort mapping of a free-text answer to standard codes, matching
+/// whole words so "GF" or "gluten free" map but "eggplant" does not.
+pub fn suggest_tags(text: &str) -> Vec<String> {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let contains_phrase = |phrase: &str| {
+        let phrase: Vec<&str> = phrase.split(' ').collect();
+        words.windows(phrase.len()).any(|window| window.iter().zip(&phrase).all(|(w, p)| w == p))
+    };
+
+    DIETARY_OPTIONS
+        .iter()
+        .filter(|o| o.keywords.iter().any(|k| contains_phrase(k)))
+        .map(|o| o.code.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::suggest_tags;
+
+    #[test]
+    fn suggest_tags_normalizes_common_spellings() {
+        assert_eq!(suggest_tags("GF"), ["gluten_free"]);
+        assert_eq!(suggest_tags("gluten free"), ["gluten_free"]);
+        assert_eq!(suggest_tags("Celiac!"), ["gluten_free"]);
+        assert_eq!(suggest_tags("vegan, no peanuts please"), ["nut_allergy", "vegan"]);
+        assert!(suggest_tags("loves eggplant and coconut").is_empty());
+        assert!(suggest_tags("none").is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+// Replies guests send to our emails
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::plain_text;
+use crate::models::{EmailReply, InboundEmailRequest};
+
+/// Split a sender like "Alex Smith <Alex@Example.com>" into its display name
+/// and lowercased address.
+pub fn parse_sender(from: &str) -> Option<(Option<String>, String)> {
+    let from = from.trim();
+    let (name, address) = match (from.rfind('<'), from.rfind('>')) {
+        (Some(open), Some(close)) if open < close => {
+            let name = from[..open].trim().trim_matches('"').trim();
+            (Some(name).filter(|n| !n.is_empty()), &from[open + 1..close])
+        }
+        _ => (None, from),
+    };
+    let address = address.trim().to_lowercase();
+    super::is_valid_email(&address).then(|| (name.map(str::to_string), address))
+}
+
+/// An inbound email from a Resend `email.received` event. Resend may leave
+/// out the body, in which case it is fetched from the receiving API.
+pub async fn from_resend_event(data: &serde_json::Value) -> Option<InboundEmailRequest> {
+    let text = |key: &str| data[key].as_str().map(str::to_string);
+    let mut email = InboundEmailRequest {
+        from: text("from")?,
+        subject: text("subject"),
+        text: text("text"),
+        html: text("html"),
+        message_id: text("email_id"),
+    };
+
+    if email.text.is_none() && email.html.is_none() {
+        if let Some(email_id) = email.message_id.as_deref() {
+            match fetch_received_body(email_id).await {
+                Ok((text, html)) => {
+                    email.text = text;
+                    email.html = html;
+                }
+                Err(e) => tracing::error!("Failed to fetch inbound email {}: {}", email_id, e),
+            }
+        }
+    }
+    Some(email)
+}
+
+async fn fetch_received_body(email_id: &str) -> Result<(Option<String>, Option<String>), String> {
+    let resend_api_key = std::env::var("RESEND_API_KEY")
+        .map_err(|_| "RESEND_API_KEY environment variable not set".to_string())?;
+    let response = super::provider::client()
+        .get(format!("https://api.resend.com/emails/receiving/{}", email_id))
+        .header("Authorization", format!("Bearer {}", resend_api_key))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Resend: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Resend API error ({})", response.status()));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Resend response: {}", e))?;
+    let text = |key: &str| body[key].as_str().map(str::to_string);
+    Ok((text("text"), text("html")))
+}
+
+/// Store a reply, matched to a guest by the sender's address. Returns None
+/// when the sender is invalid or the message was already stored.
+pub async fn record_reply(db: &PgPool, email: &InboundEmailRequest) -> Result<Option<EmailReply>, sqlx::Error> {
+    let Some((from_name, from_email)) = parse_sender(&email.from) else {
+        tracing::warn!("Ignoring inbound email with unusable sender: {}", email.from);
+        return Ok(None);
+    };
+
+    // Guests sharing an address are matched to the one still on the list
+    let guest = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+        "SELECT id, invite_id FROM guests
+         WHERE LOWER(TRIM(email)) = $1
+         ORDER BY removed, created_at
+         LIMIT 1"
+    )
+    .bind(&from_email)
+    .fetch_optional(db)
+    .await?;
+
+    let text_body = email.text.clone()
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| email.html.as_deref().map(plain_text::html_to_text));
+
+    sqlx::query_as::<_, EmailReply>(
+        "INSERT INTO email_replies
+            (guest_id, invite_id, from_email, from_name, subject, text_body, html_body, provider_message_id)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT (provider_message_id) DO NOTHING
+         RETURNING *"
+    )
+    .bind(guest.map(|(id, _)| id))
+    .bind(guest.and_then(|(_, invite_id)| invite_id))
+    .bind(&from_email)
+    .bind(&from_name)
+    .bind(&email.subject)
+    .bind(&text_body)
+    .bind(&email.html)
+    .bind(&email.message_id)
+    .fetch_optional(db)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_sender;
+
+    #[test]
+    fn sender_is_split_into_name_and_address() {
+        assert_eq!(
+            parse_sender("\"Alex Smith\" <Alex.Smith@Example.com>"),
+            Some((Some("Alex Smith".to_string()), "alex.smith@example.com".to_string())),
+        );
+        assert_eq!(parse_sender(" taylor@example.com "), Some((None, "taylor@example.com".to_string())));
+        assert_eq!(parse_sender("<taylor@example.com>"), Some((None, "taylor@example.com".to_string())));
+        assert_eq!(parse_sender("Mailer Daemon"), None);
+    }
+}
@@ -1,16 +1,57 @@
+pub mod inbound;
+pub mod plain_text;
+pub mod provider;
+pub mod suppression;
+pub mod template_store;
 pub mod templates;
 
-use crate::models::InviteWithGuests;
+use crate::calendar;
+use crate::models::{EmailCampaign, EmailReply, InviteWithGuests, RsvpDigest};
+use crate::reminders::Reminder;
+use template_store::{RenderedEmail, TemplateLinks, INVITATION_TEMPLATE};
 use sqlx::PgPool;
 use uuid::Uuid;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 pub const ONE_MONTH_REMINDER_TEMPLATE: &str = "one_month_reminder";
-pub const ONE_MONTH_REMINDER_NAME: &str = "One Month Reminder - July 2026";
-pub const ONE_MONTH_REMINDER_SUBJECT: &str = "One month to go! Sam & Jonah's wedding";
+pub const RSVP_DEADLINE_REMINDER_TEMPLATE: &str = "rsvp_deadline_reminder";
+pub const ONE_WEEK_REMINDER_TEMPLATE: &str = "one_week_reminder";
+pub const THANK_YOU_TEMPLATE: &str = "thank_you";
+
+pub const RSVP_CONFIRMATION_TEMPLATE: &str = "rsvp_confirmation";
+pub const RSVP_CONFIRMATION_NAME: &str = "RSVP Confirmations";
+pub const RSVP_CONFIRMATION_SUBJECT: &str = "We got your RSVP! Sam & Jonah's wedding";
+
+pub const INVITATION_CAMPAIGN_NAME: &str = "Wedding Invitations";
+
+pub const RSVP_DIGEST_TEMPLATE: &str = "rsvp_digest";
+pub const REPLY_FORWARD_TEMPLATE: &str = "reply_forward";
+
+/// Subject prefix for test sends
+pub const TEST_SUBJECT_PREFIX: &str = "[TEST]";
+/// Most addresses one test send may go to
+pub const MAX_TEST_RECIPIENTS: usize = 10;
+
+/// Result of sending one email
+#[derive(Debug)]
+pub struct SendOutcome {
+    /// None when every address was suppressed and nothing was sent
+    pub email_send_id: Option<Uuid>,
+    /// Addresses left off because they are on the suppression list
+    pub suppressed: Vec<String>,
+}
+
+/// Result of sending a campaign to its audience
+#[derive(Debug)]
+pub struct CampaignSendSummary {
+    pub sent_count: usize,
+    pub suppressed: Vec<String>,
+}
 
-fn one_month_reminder_idempotency_key(campaign_id: Uuid, invite_id: Uuid) -> String {
-    format!("one_month_reminder_{}_{}", campaign_id, invite_id)
+/// Valid email must contain @ and . after the @
+pub fn is_valid_email(email: &str) -> bool {
+    email.contains('@') && email.split('@').nth(1).is_some_and(|domain| domain.contains('.'))
 }
 
 #[derive(Debug, Serialize)]
@@ -19,10 +60,22 @@ struct ResendEmail {
     to: Vec<String>,
     subject: String,
     html: String,
+    /// Plain-text alternative for clients that do not show HTML
+    text: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     tags: Option<Vec<ResendTag>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_to: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<ResendAttachment>,
+}
+
+#[derive(Debug, Serialize)]
+struct ResendAttachment {
+    filename: String,
+    /// Base64-encoded file contents
+    content: String,
+    content_type: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -36,6 +89,14 @@ struct ResendResponse {
     id: String,
 }
 
+/// What a sent email is recorded against in the delivery log
+struct SendLog<'a> {
+    campaign_id: Option<Uuid>,
+    invite_id: Uuid,
+    template_type: &'a str,
+    reminder_key: Option<&'a str>,
+}
+
 pub struct EmailService {
     pub db: PgPool,
     pub frontend_url: String,
@@ -57,43 +118,358 @@ impl EmailService {
         }
     }
 
-    /// Render save-the-date email HTML
-    pub fn render_save_the_date(&self, invite: &InviteWithGuests) -> String {
-        let guest_names: Vec<String> = invite.guests.iter().map(|g| g.name.clone()).collect();
+    /// Render a stored template for an invite. `subject` overrides the
+    /// template's own subject line.
+    pub async fn render_template(
+        &self,
+        template_type: &str,
+        subject: Option<&str>,
+        invite: &InviteWithGuests,
+    ) -> Result<RenderedEmail, String> {
+        let ctx = self.template_context(invite).await?;
+        template_store::render(&self.db, template_type, subject, &ctx).await
+    }
+
+    /// Template variables for an invite.
+    pub async fn template_context(&self, invite: &InviteWithGuests) -> Result<minijinja::Value, String> {
+        let events = crate::routes::load_invite_events(&self.db, invite.invite.id)
+            .await
+            .map_err(|e| format!("Failed to load events: {}", e))?;
+        let wedding = template_store::load_variables(&self.db)
+            .await
+            .map_err(|e| format!("Failed to load template variables: {}", e))?;
+        let opt_out_tokens = sqlx::query_as::<_, (Uuid, Uuid)>(
+            "SELECT id, opt_out_token FROM guests WHERE invite_id = $1"
+        )
+        .bind(invite.invite.id)
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| format!("Failed to load opt-out links: {}", e))?
+        .into_iter()
+        .collect();
+        let api_url = std::env::var("API_URL").unwrap_or_else(|_| self.frontend_url.clone());
+        let links = TemplateLinks {
+            website_url: &self.frontend_url,
+            api_url: &api_url,
+            venue_map_url: &self.venue_map_url,
+            hotel_info_url: &self.hotel_info_url,
+        };
+        Ok(template_store::invite_context(invite, &events, &wedding, &links, &opt_out_tokens))
+    }
+
+    /// Drop suppressed addresses. Returns the addresses to send to, which
+    /// may be none, and the ones that were dropped.
+    pub async fn deliverable(
+        &self,
+        recipients: Vec<String>,
+        essential: bool,
+        invite_code: &str,
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        let suppressed = suppression::suppressed(&self.db, &recipients, essential)
+            .await
+            .map_err(|e| format!("Failed to check suppression list: {}", e))?;
+        let (send, skipped) = suppression::partition(recipients, &suppressed);
+        if send.is_empty() {
+            tracing::info!(
+                "Skipped invite {}: every address is suppressed ({})",
+                invite_code,
+                skipped.join(", ")
+            );
+        }
+        Ok((send, skipped))
+    }
+
+    /// The invite's events as an .ics attachment, when the template carries
+    /// one and at least one event has a time.
+    async fn calendar_attachments(
+        &self,
+        template_type: &str,
+        invite: &InviteWithGuests,
+    ) -> Result<Vec<ResendAttachment>, String> {
+        let attach = template_type == INVITATION_TEMPLATE
+            || crate::reminders::for_template(template_type).is_some_and(|r| r.attach_calendar);
+        if !attach {
+            return Ok(Vec::new());
+        }
 
-        templates::save_the_date_html(
-            &guest_names,
+        let events = crate::routes::load_invite_events(&self.db, invite.invite.id)
+            .await
+            .map_err(|e| format!("Failed to load events: {}", e))?;
+        if events.iter().all(|e| e.starts_at.is_none()) {
+            return Ok(Vec::new());
+        }
+        let wedding = template_store::load_variables(&self.db)
+            .await
+            .map_err(|e| format!("Failed to load template variables: {}", e))?;
+        let ics = calendar::ics(
+            &events,
+            template_store::couple(&wedding),
             &self.frontend_url,
-            &self.venue_map_url,
-            &self.hotel_info_url,
+            time::OffsetDateTime::now_utc(),
+        );
+
+        Ok(vec![ResendAttachment {
+            filename: calendar::ICS_FILENAME.to_string(),
+            content: base64::engine::general_purpose::STANDARD.encode(ics),
+            content_type: "text/calendar; charset=utf-8; method=PUBLISH".to_string(),
+        }])
+    }
+
+    /// Post an email to Resend, paced to the configured send rate and
+    /// retried when rate limited. Returns the provider's message id.
+    async fn deliver(&self, payload: &ResendEmail, idempotency_key: Option<&str>) -> Result<String, String> {
+        let mut attempt = 0;
+        let response = loop {
+            provider::throttle().await;
+            let mut request = provider::client()
+                .post("https://api.resend.com/emails")
+                .header("Authorization", format!("Bearer {}", self.resend_api_key))
+                .header("Content-Type", "application/json");
+            if let Some(key) = idempotency_key {
+                request = request.header("Idempotency-Key", key);
+            }
+            let response = request
+                .json(payload)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to send email via Resend: {}", e))?;
+
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS || attempt >= provider::MAX_RETRIES {
+                break response;
+            }
+            let retry_after = response.headers().get(reqwest::header::RETRY_AFTER).and_then(|v| v.to_str().ok());
+            let delay = provider::retry_delay(retry_after, attempt, time::OffsetDateTime::now_utc());
+            tracing::warn!("Resend rate limit hit; retrying in {:?}", delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(format!("Resend API error ({}): {}", status, error_body));
+        }
+
+        let resend_response: ResendResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Resend response: {}", e))?;
+        // The email is out either way; a missed count only affects the estimate
+        if let Err(e) = provider::record_usage(&self.db, payload.to.len()).await {
+            tracing::error!("Failed to record email quota usage: {}", e);
+        }
+        Ok(resend_response.id)
+    }
+
+    /// Add a sent email to the delivery log. A reminder retried under the
+    /// same idempotency key keeps its first entry.
+    async fn record_send(
+        &self,
+        email_send_id: Uuid,
+        payload: &ResendEmail,
+        provider_message_id: &str,
+        log: SendLog<'_>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            "INSERT INTO email_sends
+                (id, campaign_id, invite_id, sent_at, reminder_key,
+                 provider_message_id, recipients, subject, template_type)
+             VALUES ($1, $2, $3, NOW(), $4, $5, $6, $7, $8)
+             ON CONFLICT (reminder_key) WHERE reminder_key IS NOT NULL
+             DO UPDATE SET reminder_key = EXCLUDED.reminder_key"
+        )
+        .bind(email_send_id)
+        .bind(log.campaign_id)
+        .bind(log.invite_id)
+        .bind(log.reminder_key)
+        .bind(provider_message_id)
+        .bind(&payload.to)
+        .bind(&payload.subject)
+        .bind(log.template_type)
+        .execute(&self.db)
+        .await
+        .map_err(|e| format!("Failed to record email send: {}", e))?;
+        Ok(())
+    }
+
+    /// Send an email exactly as an invite would receive it, but only to the
+    /// given addresses and with a "[TEST]" subject. Nothing is recorded and
+    /// no campaign or invite tags are attached, so opens are not tracked.
+    /// Returns the subject sent.
+    pub async fn send_test(
+        &self,
+        template_type: &str,
+        subject: Option<&str>,
+        invite: &InviteWithGuests,
+        to: &[String],
+    ) -> Result<String, String> {
+        let email = self.render_template(template_type, subject, invite).await?;
+        let subject = format!("{} {}", TEST_SUBJECT_PREFIX, email.subject);
+
+        let email_payload = ResendEmail {
+            from: format!("Sam & Jonah <{}>", self.from_email),
+            to: to.to_vec(),
+            subject: subject.clone(),
+            html: email.html,
+            text: email.text,
+            tags: Some(vec![
+                ResendTag {
+                    name: "template".to_string(),
+                    value: template_type.to_string(),
+                },
+                ResendTag {
+                    name: "test".to_string(),
+                    value: "true".to_string(),
+                },
+            ]),
+            reply_to: Some(vec![self.from_email.clone()]),
+            attachments: self.calendar_attachments(template_type, invite).await?,
+        };
+
+        self.deliver(&email_payload, None).await?;
+
+        tracing::info!(
+            "Sent test {} email for invite {} to {}",
+            template_type,
+            invite.invite.unique_code,
+            to.join(", ")
+        );
+
+        Ok(subject)
+    }
+
+    /// The campaign every invitation is recorded against, created on first
+    /// use. Invitations take their subject from the invitation template; the
+    /// campaign's subject is only its label.
+    pub async fn invitation_campaign(&self) -> Result<EmailCampaign, String> {
+        sqlx::query_as::<_, EmailCampaign>(
+            "INSERT INTO email_campaigns (name, subject, template_type)
+             SELECT $1, COALESCE((SELECT subject FROM email_templates WHERE template_type = $2), $1), $2
+             ON CONFLICT (template_type) WHERE template_type = 'invitation'
+             DO UPDATE SET template_type = EXCLUDED.template_type
+             RETURNING *"
         )
+        .bind(INVITATION_CAMPAIGN_NAME)
+        .bind(INVITATION_TEMPLATE)
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| format!("Failed to create invitation campaign: {}", e))
     }
 
-    /// Render the one-month reminder for confirmed attendees only.
-    pub fn render_one_month_reminder(&self, invite: &InviteWithGuests) -> String {
-        let guest_names: Vec<String> = invite.guests.iter().map(|g| g.name.clone()).collect();
+    /// Send the invitation to every guest on an invite, record it against
+    /// the invitation campaign and mark the invite as sent. Sending again
+    /// records another send.
+    pub async fn send_invitation(&self, campaign: &EmailCampaign, invite: &InviteWithGuests) -> Result<SendOutcome, String> {
+        let email_send_id = Uuid::new_v4();
+        let recipient_emails: Vec<String> = invite.guests.iter()
+            .filter(|g| is_valid_email(&g.email))
+            .map(|g| g.email.clone())
+            .collect();
+
+        if recipient_emails.is_empty() {
+            return Err(format!("No valid emails for invite {}", invite.invite.unique_code));
+        }
+        let (recipient_emails, suppressed) = self
+            .deliverable(recipient_emails, true, &invite.invite.unique_code)
+            .await?;
+        if recipient_emails.is_empty() {
+            return Ok(SendOutcome { email_send_id: None, suppressed });
+        }
+        let email = self.render_template(INVITATION_TEMPLATE, None, invite).await?;
+
+        let email_payload = ResendEmail {
+            from: format!("Sam & Jonah <{}>", self.from_email),
+            to: recipient_emails,
+            subject: email.subject,
+            html: email.html,
+            text: email.text,
+            tags: Some(vec![
+                ResendTag {
+                    name: "campaign_id".to_string(),
+                    value: campaign.id.to_string(),
+                },
+                ResendTag {
+                    name: "invite_id".to_string(),
+                    value: invite.invite.id.to_string(),
+                },
+                ResendTag {
+                    name: "template".to_string(),
+                    value: INVITATION_TEMPLATE.to_string(),
+                },
+            ]),
+            reply_to: Some(vec![self.from_email.clone()]),
+            attachments: self.calendar_attachments(INVITATION_TEMPLATE, invite).await?,
+        };
+
+        let provider_message_id = self.deliver(&email_payload, None).await?;
+
+        // The invitation is out, so bookkeeping failures are logged rather
+        // than reported as a failed send that an admin might repeat
+        if let Err(e) = self.record_send(email_send_id, &email_payload, &provider_message_id, SendLog {
+            campaign_id: Some(campaign.id),
+            invite_id: invite.invite.id,
+            template_type: INVITATION_TEMPLATE,
+            reminder_key: None,
+        })
+        .await
+        {
+            tracing::error!("Invitation for invite {} was sent but not logged: {}", invite.invite.unique_code, e);
+        }
+
+        if let Err(e) = sqlx::query("UPDATE invites SET invite_sent_at = NOW() WHERE id = $1")
+            .bind(invite.invite.id)
+            .execute(&self.db)
+            .await
+        {
+            tracing::error!("Failed to mark invite {} as sent: {}", invite.invite.unique_code, e);
+        }
+
+        tracing::info!(
+            "Sent invitation to {} (invite: {}, resend_id: {})",
+            email_payload.to.join(", "),
+            invite.invite.unique_code,
+            provider_message_id
+        );
 
-        templates::one_month_reminder_html(&guest_names, &self.frontend_url)
+        Ok(SendOutcome { email_send_id: Some(email_send_id), suppressed })
     }
 
-    /// Send save-the-date email via Resend API
-    pub async fn send_save_the_date(
+    /// Bring a campaign's sent count up to date after a send. Sends from
+    /// earlier runs are included.
+    pub async fn update_campaign_totals(&self, campaign_id: Uuid, sent_now: usize) -> Result<(), String> {
+        sqlx::query(
+            "UPDATE email_campaigns
+             SET sent_count = (
+                 SELECT COUNT(*)::INT FROM email_sends WHERE campaign_id = $1
+             ),
+             sent_at = CASE WHEN $2 > 0 THEN NOW() ELSE sent_at END
+             WHERE id = $1"
+        )
+        .bind(campaign_id)
+        .bind(sent_now as i32)
+        .execute(&self.db)
+        .await
+        .map_err(|e| format!("Failed to update campaign: {}", e))?;
+        Ok(())
+    }
+
+    /// Send a campaign's email to one invite via Resend API
+    pub async fn send_campaign_email(
         &self,
-        campaign_id: Uuid,
+        campaign: &EmailCampaign,
         invite: &InviteWithGuests,
-        subject: &str,
-    ) -> Result<Uuid, String> {
+    ) -> Result<SendOutcome, String> {
+        let campaign_id = campaign.id;
         // Generate email send ID for tracking
         let email_send_id = Uuid::new_v4();
 
-        // Render HTML
-        let html = self.render_save_the_date(invite);
+        // Render subject and HTML
+        let email = self.render_template(&campaign.template_type, Some(&campaign.subject), invite).await?;
 
         // Get all valid recipient emails from guests in this invite
         let recipient_emails: Vec<String> = invite.guests.iter()
             .filter(|guest| {
-                // Valid email must contain @ and . after the @
-                let is_valid = guest.email.contains('@') && guest.email.split('@').nth(1).map_or(false, |domain| domain.contains('.'));
+                let is_valid = is_valid_email(&guest.email);
                 if !is_valid {
                     tracing::debug!("Skipping invalid email for {}: {}", guest.name, guest.email);
                 }
@@ -106,13 +482,20 @@ impl EmailService {
             let guest_names: Vec<String> = invite.guests.iter().map(|g| format!("{} ({})", g.name, g.email)).collect();
             return Err(format!("No valid email addresses found for invite with guests: {}", guest_names.join(", ")));
         }
+        let (recipient_emails, suppressed) = self
+            .deliverable(recipient_emails, false, &invite.invite.unique_code)
+            .await?;
+        if recipient_emails.is_empty() {
+            return Ok(SendOutcome { email_send_id: None, suppressed });
+        }
 
         // Prepare email payload for Resend with tags for tracking
         let email_payload = ResendEmail {
             from: format!("Sam & Jonah <{}>", self.from_email),
             to: recipient_emails.clone(),
-            subject: subject.to_string(),
-            html,
+            subject: email.subject,
+            html: email.html,
+            text: email.text,
             tags: Some(vec![
                 ResendTag {
                     name: "campaign_id".to_string(),
@@ -124,69 +507,47 @@ impl EmailService {
                 },
             ]),
             reply_to: Some(vec![self.from_email.clone()]),
+            attachments: self.calendar_attachments(&campaign.template_type, invite).await?,
         };
 
         // Send via Resend API
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://api.resend.com/emails")
-            .header("Authorization", format!("Bearer {}", self.resend_api_key))
-            .header("Content-Type", "application/json")
-            .json(&email_payload)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send email via Resend: {}", e))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Resend API error ({}): {}", status, error_body));
-        }
-
-        let resend_response: ResendResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse Resend response: {}", e))?;
+        let provider_message_id = self.deliver(&email_payload, None).await?;
 
         tracing::info!(
-            "📧 Sent save-the-date email to {} (invite: {}, resend_id: {})",
+            "📧 Sent {} email to {} (invite: {}, resend_id: {})",
+            campaign.template_type,
             recipient_emails.join(", "),
             invite.invite.unique_code,
-            resend_response.id
+            provider_message_id
         );
 
-        // Record the email send in database
-        sqlx::query(
-            "INSERT INTO email_sends (id, campaign_id, invite_id, sent_at)
-             VALUES ($1, $2, $3, NOW())"
-        )
-        .bind(email_send_id)
-        .bind(campaign_id)
-        .bind(invite.invite.id)
-        .execute(&self.db)
-        .await
-        .map_err(|e| format!("Failed to record email send: {}", e))?;
+        // Record the email send in the delivery log
+        self.record_send(email_send_id, &email_payload, &provider_message_id, SendLog {
+            campaign_id: Some(campaign_id),
+            invite_id: invite.invite.id,
+            template_type: &campaign.template_type,
+            reminder_key: None,
+        })
+        .await?;
 
-        Ok(email_send_id)
+        Ok(SendOutcome { email_send_id: Some(email_send_id), suppressed })
     }
 
-    /// Send the one-month reminder to the confirmed attendees on an invite.
-    pub async fn send_one_month_reminder(
+    /// Send a reminder to the guests on an invite it is addressed to.
+    pub async fn send_reminder(
         &self,
+        reminder: &Reminder,
         campaign_id: Uuid,
         invite: &InviteWithGuests,
         subject: &str,
-    ) -> Result<Uuid, String> {
+    ) -> Result<SendOutcome, String> {
         let email_send_id = Uuid::new_v4();
-        let html = self.render_one_month_reminder(invite);
-        let idempotency_key = one_month_reminder_idempotency_key(
-            campaign_id,
-            invite.invite.id,
-        );
+        let email = self.render_template(reminder.template_type, Some(subject), invite).await?;
+        let idempotency_key = reminder.idempotency_key(campaign_id, invite.invite.id);
 
         let recipient_emails: Vec<String> = invite.guests.iter()
             .filter(|guest| {
-                let is_valid = guest.email.contains('@') && guest.email.split('@').nth(1).map_or(false, |domain| domain.contains('.'));
+                let is_valid = is_valid_email(&guest.email);
                 if !is_valid {
                     tracing::debug!("Skipping invalid email for {}: {}", guest.name, guest.email);
                 }
@@ -197,16 +558,24 @@ impl EmailService {
 
         if recipient_emails.is_empty() {
             return Err(format!(
-                "No valid email addresses found for attending guests on invite {}",
+                "No valid email addresses found for {} recipients on invite {}",
+                reminder.key,
                 invite.invite.unique_code
             ));
         }
+        let (recipient_emails, suppressed) = self
+            .deliverable(recipient_emails, false, &invite.invite.unique_code)
+            .await?;
+        if recipient_emails.is_empty() {
+            return Ok(SendOutcome { email_send_id: None, suppressed });
+        }
 
         let email_payload = ResendEmail {
             from: format!("Sam & Jonah <{}>", self.from_email),
             to: recipient_emails.clone(),
-            subject: subject.to_string(),
-            html,
+            subject: email.subject,
+            html: email.html,
+            text: email.text,
             tags: Some(vec![
                 ResendTag {
                     name: "campaign_id".to_string(),
@@ -218,115 +587,217 @@ impl EmailService {
                 },
                 ResendTag {
                     name: "template".to_string(),
-                    value: ONE_MONTH_REMINDER_TEMPLATE.to_string(),
+                    value: reminder.template_type.to_string(),
                 },
             ]),
             reply_to: Some(vec![self.from_email.clone()]),
+            attachments: self.calendar_attachments(reminder.template_type, invite).await?,
         };
 
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://api.resend.com/emails")
-            .header("Authorization", format!("Bearer {}", self.resend_api_key))
-            .header("Content-Type", "application/json")
-            .header("Idempotency-Key", &idempotency_key)
-            .json(&email_payload)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to send email via Resend: {}", e))?;
+        let provider_message_id = self.deliver(&email_payload, Some(&idempotency_key)).await?;
+        self.record_send(email_send_id, &email_payload, &provider_message_id, SendLog {
+            campaign_id: Some(campaign_id),
+            invite_id: invite.invite.id,
+            template_type: reminder.template_type,
+            reminder_key: Some(&idempotency_key),
+        })
+        .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(format!("Resend API error ({}): {}", status, error_body));
+        tracing::info!(
+            "Sent {} reminder to {} (invite: {}, resend_id: {})",
+            reminder.key,
+            recipient_emails.join(", "),
+            invite.invite.unique_code,
+            provider_message_id
+        );
+
+        Ok(SendOutcome { email_send_id: Some(email_send_id), suppressed })
+    }
+
+    /// Send an RSVP confirmation to every guest on the invite with an email.
+    /// Returns None when every address is suppressed.
+    pub async fn send_rsvp_confirmation(
+        &self,
+        campaign_id: Uuid,
+        invite: &InviteWithGuests,
+        summaries: &[templates::RsvpSummary],
+    ) -> Result<Option<Uuid>, String> {
+        let email_send_id = Uuid::new_v4();
+        let edit_link = format!(
+            "{}/rsvp?code={}",
+            self.frontend_url.trim_end_matches('/'),
+            invite.invite.unique_code
+        );
+        let html = templates::rsvp_confirmation_html(summaries, &edit_link);
+        let text = plain_text::html_to_text(&html);
+
+        let recipient_emails: Vec<String> = invite.guests.iter()
+            .filter(|guest| is_valid_email(&guest.email))
+            .map(|guest| guest.email.clone())
+            .collect();
+
+        if recipient_emails.is_empty() {
+            return Err(format!(
+                "No valid email addresses found for invite {}",
+                invite.invite.unique_code
+            ));
+        }
+        let (recipient_emails, suppressed) = self
+            .deliverable(recipient_emails, true, &invite.invite.unique_code)
+            .await?;
+        if !suppressed.is_empty() {
+            tracing::info!("Skipped suppressed addresses for RSVP confirmation: {}", suppressed.join(", "));
+        }
+        if recipient_emails.is_empty() {
+            return Ok(None);
         }
 
-        let resend_response: ResendResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse Resend response: {}", e))?;
+        let email_payload = ResendEmail {
+            from: format!("Sam & Jonah <{}>", self.from_email),
+            to: recipient_emails.clone(),
+            subject: RSVP_CONFIRMATION_SUBJECT.to_string(),
+            html,
+            text,
+            tags: Some(vec![
+                ResendTag {
+                    name: "campaign_id".to_string(),
+                    value: campaign_id.to_string(),
+                },
+                ResendTag {
+                    name: "invite_id".to_string(),
+                    value: invite.invite.id.to_string(),
+                },
+                ResendTag {
+                    name: "template".to_string(),
+                    value: RSVP_CONFIRMATION_TEMPLATE.to_string(),
+                },
+            ]),
+            reply_to: Some(vec![self.from_email.clone()]),
+            attachments: Vec::new(),
+        };
 
-        sqlx::query(
-            "INSERT INTO email_sends (id, campaign_id, invite_id, sent_at, reminder_key)
-             VALUES ($1, $2, $3, NOW(), $4)
-             ON CONFLICT (reminder_key) WHERE reminder_key IS NOT NULL
-             DO UPDATE SET reminder_key = EXCLUDED.reminder_key"
-        )
-        .bind(email_send_id)
-        .bind(campaign_id)
-        .bind(invite.invite.id)
-        .bind(&idempotency_key)
-        .execute(&self.db)
-        .await
-        .map_err(|e| format!("Failed to record email send: {}", e))?;
+        let provider_message_id = self.deliver(&email_payload, None).await?;
+
+        self.record_send(email_send_id, &email_payload, &provider_message_id, SendLog {
+            campaign_id: Some(campaign_id),
+            invite_id: invite.invite.id,
+            template_type: RSVP_CONFIRMATION_TEMPLATE,
+            reminder_key: None,
+        })
+        .await?;
 
         tracing::info!(
-            "Sent one-month reminder to {} (invite: {}, resend_id: {})",
+            "Sent RSVP confirmation to {} (invite: {}, resend_id: {})",
             recipient_emails.join(", "),
             invite.invite.unique_code,
-            resend_response.id
+            provider_message_id
         );
 
-        Ok(email_send_id)
+        Ok(Some(email_send_id))
+    }
+
+    /// Send an RSVP notification or digest to the couple.
+    pub async fn send_rsvp_digest(
+        &self,
+        recipients: &[String],
+        subject: &str,
+        digest: &RsvpDigest,
+    ) -> Result<(), String> {
+        let admin_url = format!("{}/admin", self.frontend_url.trim_end_matches('/'));
+        let html = templates::rsvp_digest_html(subject, digest, &admin_url);
+        let text = plain_text::html_to_text(&html);
+
+        let email_payload = ResendEmail {
+            from: format!("Sam & Jonah <{}>", self.from_email),
+            to: recipients.to_vec(),
+            subject: subject.to_string(),
+            html,
+            text,
+            tags: Some(vec![ResendTag {
+                name: "template".to_string(),
+                value: RSVP_DIGEST_TEMPLATE.to_string(),
+            }]),
+            reply_to: None,
+            attachments: Vec::new(),
+        };
+
+        self.deliver(&email_payload, None).await?;
+
+        tracing::info!("Sent RSVP notification \"{}\" to {}", subject, recipients.join(", "));
+
+        Ok(())
+    }
+
+    /// Forward a guest's reply to the couple. Replying to the forward
+    /// answers the guest.
+    pub async fn send_reply_forward(
+        &self,
+        recipients: &[String],
+        reply: &EmailReply,
+        guest_name: Option<&str>,
+    ) -> Result<(), String> {
+        let sender = guest_name.or(reply.from_name.as_deref()).unwrap_or(&reply.from_email);
+        let admin_url = format!("{}/admin", self.frontend_url.trim_end_matches('/'));
+        let html = templates::reply_forward_html(reply, sender, &admin_url);
+        let text = plain_text::html_to_text(&html);
+
+        let email_payload = ResendEmail {
+            from: format!("Sam & Jonah <{}>", self.from_email),
+            to: recipients.to_vec(),
+            subject: format!("Reply from {}: {}", sender, reply.subject.as_deref().unwrap_or("(no subject)")),
+            html,
+            text,
+            tags: Some(vec![ResendTag {
+                name: "template".to_string(),
+                value: REPLY_FORWARD_TEMPLATE.to_string(),
+            }]),
+            reply_to: Some(vec![reply.from_email.clone()]),
+            attachments: Vec::new(),
+        };
+
+        self.deliver(&email_payload, None).await?;
+        tracing::info!("Forwarded reply from {} to {}", reply.from_email, recipients.join(", "));
+
+        Ok(())
     }
 
     /// Send campaign to all invites
-    pub async fn send_campaign(&self, campaign_id: Uuid) -> Result<usize, String> {
-        // Get campaign details for subject
-        let campaign = sqlx::query!(
-            "SELECT subject FROM email_campaigns WHERE id = $1",
-            campaign_id
+    pub async fn send_campaign(&self, campaign_id: Uuid) -> Result<CampaignSendSummary, String> {
+        // Get campaign details for subject and template
+        let campaign = sqlx::query_as::<_, EmailCampaign>(
+            "SELECT * FROM email_campaigns WHERE id = $1"
         )
+        .bind(campaign_id)
         .fetch_one(&self.db)
         .await
         .map_err(|e| format!("Failed to fetch campaign: {}", e))?;
 
-        // Get all invites with non-removed guests that haven't been sent yet
-        let invites = sqlx::query!(
-            "SELECT DISTINCT i.* FROM invites i
-             INNER JOIN guests g ON g.invite_id = i.id
-             WHERE g.removed = false
-             AND NOT EXISTS (
-                 SELECT 1 FROM email_sends es
-                 WHERE es.invite_id = i.id AND es.campaign_id = $1
-             )",
-            campaign_id
-        )
-        .fetch_all(&self.db)
-        .await
-        .map_err(|e| format!("Failed to fetch invites: {}", e))?;
+        // Invites in the campaign's audience that haven't been sent yet,
+        // with only the guests it targets
+        let invites = crate::segments::campaign_audience(&self.db, &campaign)
+            .await
+            .map_err(|e| format!("Failed to fetch invites: {}", e))?;
 
         let mut sent_count = 0;
+        let mut suppressed = Vec::new();
         let mut errors = Vec::new();
 
-        for invite_row in invites {
-            // Get guests for this invite
-            let guests = sqlx::query_as::<_, crate::models::Guest>(
-                "SELECT * FROM guests WHERE invite_id = $1 AND removed = false"
-            )
-            .bind(invite_row.id)
-            .fetch_all(&self.db)
-            .await
-            .map_err(|e| format!("Failed to fetch guests: {}", e))?;
-
-            let unique_code = invite_row.unique_code.clone();
-            let invite = InviteWithGuests {
-                invite: crate::models::Invite {
-                    id: invite_row.id,
-                    unique_code: invite_row.unique_code,
-                    invite_type: invite_row.invite_type.unwrap_or_else(|| "single".to_string()),
-                    invite_sent_at: None,
-                    created_at: invite_row.created_at,
-                    updated_at: invite_row.updated_at,
-                },
-                guests,
-            };
+        for invite in invites {
+            let unique_code = invite.invite.unique_code.clone();
 
             // Send email - continue on error instead of stopping
-            match self.send_save_the_date(campaign_id, &invite, &campaign.subject).await {
-                Ok(_) => {
-                    sent_count += 1;
-                    tracing::info!("✓ Successfully sent email for invite {}", unique_code);
+            let sent = if campaign.template_type == INVITATION_TEMPLATE {
+                self.send_invitation(&campaign, &invite).await
+            } else {
+                self.send_campaign_email(&campaign, &invite).await
+            };
+            match sent {
+                Ok(outcome) => {
+                    if let Some(email_send_id) = outcome.email_send_id {
+                        sent_count += 1;
+                        tracing::info!("✓ Successfully sent email for invite {} (send {})", unique_code, email_send_id);
+                    }
+                    suppressed.extend(outcome.suppressed);
                 }
                 Err(e) => {
                     let error_msg = format!("Failed to send to invite {}: {}", unique_code, e);
@@ -336,44 +807,16 @@ impl EmailService {
             }
         }
 
-        // Update campaign
-        sqlx::query(
-            "UPDATE email_campaigns
-             SET sent_count = $1, sent_at = NOW()
-             WHERE id = $2"
-        )
-        .bind(sent_count as i32)
-        .bind(campaign_id)
-        .execute(&self.db)
-        .await
-        .map_err(|e| format!("Failed to update campaign: {}", e))?;
+        self.update_campaign_totals(campaign_id, sent_count).await?;
 
         // Return success if at least some emails were sent, or error if all failed
         if sent_count == 0 && !errors.is_empty() {
             Err(format!("Failed to send all emails. Errors: {}", errors.join("; ")))
         } else if !errors.is_empty() {
             tracing::warn!("Campaign completed with {} successes and {} errors", sent_count, errors.len());
-            Ok(sent_count)
+            Ok(CampaignSendSummary { sent_count, suppressed })
         } else {
-            Ok(sent_count)
+            Ok(CampaignSendSummary { sent_count, suppressed })
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::one_month_reminder_idempotency_key;
-    use uuid::Uuid;
-
-    #[test]
-    fn reminder_idempotency_key_is_stable_per_campaign_and_invite() {
-        let campaign_id = Uuid::parse_str("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa").unwrap();
-        let invite_id = Uuid::parse_str("bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb").unwrap();
-
-        let first = one_month_reminder_idempotency_key(campaign_id, invite_id);
-        let retry = one_month_reminder_idempotency_key(campaign_id, invite_id);
-
-        assert_eq!(first, retry);
-        assert!(first.len() <= 256);
-    }
-}
@@ -0,0 +1,207 @@
+// Plain-text versions of HTML emails
+
+/// Tags that start a new line in the text version
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "tr", "table", "h1", "h2", "h3", "h4", "h5", "h6", "li", "ul", "ol", "blockquote",
+];
+
+/// Elements whose content is never shown as text
+const HIDDEN_TAGS: &[&str] = &["head", "style", "script", "title"];
+
+/// Convert an email's HTML into readable plain text: block elements become
+/// line breaks, links keep their address, images keep their alt text and
+/// entities are decoded.
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    // Link address and the length of `out` where its text started
+    let mut link: Option<(String, usize)> = None;
+
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        if tag.starts_with('!') {
+            continue;
+        }
+        let closing = tag.starts_with('/');
+        let name = tag.trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if !closing && HIDDEN_TAGS.contains(&name.as_str()) {
+            let close = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&close) {
+                Some(at) => rest[at..].find('>').map_or("", |gt| &rest[at + gt + 1..]),
+                None => "",
+            };
+            continue;
+        }
+
+        match name.as_str() {
+            "br" => out.push('\n'),
+            "td" | "th" if closing => out.push(' '),
+            "img" => {
+                if let Some(alt) = attribute(tag, "alt").filter(|alt| !alt.trim().is_empty()) {
+                    push_text(&mut out, &alt);
+                    out.push('\n');
+                }
+            }
+            "a" if !closing => link = attribute(tag, "href").map(|href| (decode_entities(&href), out.len())),
+            "a" => {
+                if let Some((href, text_start)) = link.take() {
+                    let text = out[text_start..].trim();
+                    if !href.is_empty() && !href.starts_with('#') && text != href {
+                        out.push_str(&format!(" ({})", href));
+                    }
+                }
+            }
+            name if BLOCK_TAGS.contains(&name) => out.push('\n'),
+            _ => {}
+        }
+    }
+    push_text(&mut out, rest);
+
+    tidy(&out)
+}
+
+fn push_text(out: &mut String, text: &str) {
+    let text = decode_entities(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+    if text.is_empty() {
+        if text_ends_mid_line(out) && !out.ends_with(' ') {
+            out.push(' ');
+        }
+        return;
+    }
+    out.push_str(&text);
+}
+
+fn text_ends_mid_line(out: &str) -> bool {
+    !out.is_empty() && !out.ends_with('\n')
+}
+
+/// Value of a quoted attribute in a tag, e.g. `href="..."`.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let at = search + found;
+        search = at + name.len();
+        let preceded = at == 0 || lower.as_bytes()[at - 1].is_ascii_whitespace();
+        let after = lower[search..].trim_start();
+        if !preceded || !after.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - after.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return value.split_whitespace().next().map(str::to_string);
+        }
+        return value[1..].find(quote).map(|end| value[1..1 + end].to_string());
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "mdash" => '—',
+        "ndash" => '–',
+        "bull" => '•',
+        "hellip" => '…',
+        "rsquo" => '’',
+        "lsquo" => '‘',
+        "rdquo" => '”',
+        "ldquo" => '“',
+        _ => return None,
+    })
+}
+
+/// Trim every line and keep at most one blank line between paragraphs.
+fn tidy(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::html_to_text;
+
+    #[test]
+    fn converts_email_html_to_readable_text() {
+        let html = r#"<!DOCTYPE html><html><head><title>Hi</title><style>p { color: red; }</style></head>
+            <body><table><tr><td>
+                <img src="x.jpg" alt="Sam &amp; Jonah" />
+                <p>Dear Alex and Taylor,</p>
+                <p>We can hardly wait&mdash;see you<br>soon!</p>
+                <a href="https://samandjonah.com/rsvp?code=ABC&amp;x=1">RSVP Now</a>
+                <a href="https://samandjonah.com">https://samandjonah.com</a>
+            </td></tr></table></body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Sam & Jonah\n\n\
+             Dear Alex and Taylor,\n\n\
+             We can hardly wait—see you\n\
+             soon!\n\
+             RSVP Now (https://samandjonah.com/rsvp?code=ABC&x=1) https://samandjonah.com\n",
+        );
+    }
+}
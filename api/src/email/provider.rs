@@ -0,0 +1,164 @@
+// Talking to the email provider: one shared HTTP client, paced sends with
+// backoff when rate limited, and the monthly quota
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::time::Instant;
+
+use crate::models::EmailQuota;
+
+/// Resend's default API rate limit
+pub const DEFAULT_SENDS_PER_SECOND: f64 = 2.0;
+/// Emails a month on Resend's free tier
+pub const DEFAULT_MONTHLY_QUOTA: i64 = 3000;
+/// Times a rate-limited request is retried before giving up
+pub const MAX_RETRIES: u32 = 3;
+/// Longest wait honored from a Retry-After header
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+/// Warn once less than this share of the quota would be left
+const LOW_QUOTA_SHARE: f64 = 0.1;
+
+/// The HTTP client every provider request goes through, so connections are
+/// reused across a send.
+pub fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("HTTP client builds")
+    })
+}
+
+/// Sends allowed per second, from EMAIL_SENDS_PER_SECOND.
+pub fn sends_per_second() -> f64 {
+    std::env::var("EMAIL_SENDS_PER_SECOND")
+        .ok()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|rate| rate.is_finite() && *rate > 0.0)
+        .unwrap_or(DEFAULT_SENDS_PER_SECOND)
+}
+
+/// Emails allowed per month, from EMAIL_MONTHLY_QUOTA.
+pub fn monthly_quota() -> i64 {
+    std::env::var("EMAIL_MONTHLY_QUOTA")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|quota| *quota > 0)
+        .unwrap_or(DEFAULT_MONTHLY_QUOTA)
+}
+
+/// Wait for the next send slot. Slots are shared by every send in the
+/// process, so concurrent sends are paced together.
+pub async fn throttle() {
+    static NEXT_SLOT: Mutex<Option<Instant>> = Mutex::new(None);
+    let interval = Duration::from_secs_f64(1.0 / sends_per_second());
+
+    let slot = {
+        let mut next = NEXT_SLOT.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = next.map_or_else(Instant::now, |n| n.max(Instant::now()));
+        *next = Some(slot + interval);
+        slot
+    };
+    tokio::time::sleep_until(slot).await;
+}
+
+/// How long to wait before retrying a rate-limited request. Honors a
+/// Retry-After of seconds or an HTTP date, otherwise backs off
+/// exponentially from one second.
+pub fn retry_delay(retry_after: Option<&str>, attempt: u32, now: time::OffsetDateTime) -> Duration {
+    let from_header = retry_after.map(str::trim).and_then(|value| {
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        let at = time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc2822).ok()?;
+        Some((at - now).try_into().unwrap_or(Duration::ZERO))
+    });
+    from_header
+        .unwrap_or_else(|| Duration::from_secs(1 << attempt.min(5)))
+        .min(MAX_RETRY_WAIT)
+}
+
+/// Count emails the provider accepted against this month's quota.
+pub async fn record_usage(db: &PgPool, emails: usize) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO email_provider_usage (month, email_count)
+         VALUES (date_trunc('month', NOW())::date, $1)
+         ON CONFLICT (month)
+         DO UPDATE SET email_count = email_provider_usage.email_count + EXCLUDED.email_count,
+                       updated_at = NOW()"
+    )
+    .bind(emails as i32)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// This month's quota and how much of it is used.
+pub async fn usage(db: &PgPool) -> Result<EmailQuota, sqlx::Error> {
+    let (month, used) = sqlx::query_as::<_, (String, i64)>(
+        "SELECT to_char(NOW(), 'YYYY-MM'),
+                COALESCE((SELECT email_count FROM email_provider_usage
+                          WHERE month = date_trunc('month', NOW())::date), 0)::BIGINT"
+    )
+    .fetch_one(db)
+    .await?;
+    let limit = monthly_quota();
+
+    Ok(EmailQuota {
+        month,
+        limit,
+        used,
+        remaining: (limit - used).max(0),
+    })
+}
+
+/// Why sending `planned` more emails this month must be refused, if it must.
+pub fn refusal(quota: &EmailQuota, planned: usize) -> Option<String> {
+    let planned = planned as i64;
+    (planned > quota.remaining).then(|| {
+        format!(
+            "This send needs up to {} emails but only {} of the {} monthly emails are left for {}",
+            planned, quota.remaining, quota.limit, quota.month
+        )
+    })
+}
+
+/// A warning when sending `planned` more emails would leave little of this
+/// month's quota.
+pub fn warning(quota: &EmailQuota, planned: usize) -> Option<String> {
+    let left = quota.remaining - planned as i64;
+    (left >= 0 && (left as f64) < quota.limit as f64 * LOW_QUOTA_SHARE).then(|| {
+        format!("Only {} of the {} monthly emails will be left for {}", left, quota.limit, quota.month)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{refusal, retry_delay, warning, MAX_RETRY_WAIT};
+    use crate::models::EmailQuota;
+    use std::time::Duration;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn retry_after_is_honored() {
+        // Wed, 21 Oct 2015 07:27:50 GMT
+        let now = OffsetDateTime::from_unix_timestamp(1_445_412_470).unwrap();
+        assert_eq!(retry_delay(Some("3"), 0, now), Duration::from_secs(3));
+        assert_eq!(retry_delay(Some("Wed, 21 Oct 2015 07:28:00 GMT"), 0, now), Duration::from_secs(10));
+        assert_eq!(retry_delay(Some("soon"), 2, now), Duration::from_secs(4));
+        assert_eq!(retry_delay(None, 0, now), Duration::from_secs(1));
+        assert_eq!(retry_delay(Some("3600"), 0, now), MAX_RETRY_WAIT);
+    }
+
+    #[test]
+    fn quota_refuses_overruns_and_warns_when_low() {
+        let quota = EmailQuota { month: "2026-10".to_string(), limit: 3000, used: 2650, remaining: 350 };
+        assert!(refusal(&quota, 350).is_none());
+        assert!(refusal(&quota, 351).is_some());
+        assert!(warning(&quota, 40).is_none());
+        assert!(warning(&quota, 100).is_some());
+    }
+}
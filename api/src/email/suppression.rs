@@ -0,0 +1,74 @@
+// Addresses we must not email: bounces, complaints, manual blocks and opt-outs
+
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::models::SUPPRESS_OPT_OUT;
+
+/// Which of `emails` are suppressed, lowercased. Opt-outs only count for
+/// non-essential email; invitations and RSVP confirmations are essential.
+pub async fn suppressed(db: &PgPool, emails: &[String], essential: bool) -> Result<HashSet<String>, sqlx::Error> {
+    let emails: Vec<String> = emails.iter().map(|e| e.trim().to_lowercase()).collect();
+    let rows = sqlx::query_scalar::<_, String>(
+        "SELECT email FROM email_suppressions
+         WHERE email = ANY($1) AND ($2 = false OR reason <> $3)"
+    )
+    .bind(&emails)
+    .bind(essential)
+    .bind(SUPPRESS_OPT_OUT)
+    .fetch_all(db)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Split recipients into those we can send to and those that are suppressed.
+pub fn partition(recipients: Vec<String>, suppressed: &HashSet<String>) -> (Vec<String>, Vec<String>) {
+    recipients.into_iter().partition(|email| !suppressed.contains(&email.trim().to_lowercase()))
+}
+
+/// Add an address to the list. A stronger reason replaces an opt-out, but an
+/// opt-out never replaces a bounce, complaint or manual block.
+pub async fn suppress(
+    db: &PgPool,
+    email: &str,
+    reason: &str,
+    detail: Option<&str>,
+    guest_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO email_suppressions (email, reason, detail, guest_id)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (email) DO UPDATE
+         SET reason = EXCLUDED.reason, detail = EXCLUDED.detail,
+             guest_id = COALESCE(EXCLUDED.guest_id, email_suppressions.guest_id),
+             created_at = NOW()
+         WHERE email_suppressions.reason = $5 AND EXCLUDED.reason <> $5"
+    )
+    .bind(email.trim().to_lowercase())
+    .bind(reason)
+    .bind(detail)
+    .bind(guest_id)
+    .bind(SUPPRESS_OPT_OUT)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::partition;
+    use std::collections::HashSet;
+
+    #[test]
+    fn partition_ignores_case_and_keeps_order() {
+        let suppressed: HashSet<String> = ["bounced@example.com".to_string()].into_iter().collect();
+        let (send, skipped) = partition(
+            vec!["alex@example.com".to_string(), "Bounced@Example.com".to_string(), "sam@example.com".to_string()],
+            &suppressed,
+        );
+
+        assert_eq!(send, ["alex@example.com", "sam@example.com"]);
+        assert_eq!(skipped, ["Bounced@Example.com"]);
+    }
+}
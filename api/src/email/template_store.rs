@@ -0,0 +1,448 @@
+// Editable email templates stored in the database and rendered with MiniJinja
+
+use minijinja::{context, Environment, Value};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::calendar;
+use crate::models::{EmailTemplate, EmailTemplateVariable, Event, InviteWithGuests, TemplateVariableDoc};
+
+pub const INVITATION_TEMPLATE: &str = "invitation";
+pub const SAVE_THE_DATE_TEMPLATE: &str = "save_the_date";
+
+/// Built-in templates seeded on startup: (template type, name, subject, source).
+const DEFAULT_TEMPLATES: &[(&str, &str, &str, &str)] = &[
+    (
+        INVITATION_TEMPLATE,
+        "Wedding Invitation",
+        "You're Invited! {{ wedding.couple }}'s Wedding",
+        include_str!("../../templates/email/invitation.html"),
+    ),
+    (
+        SAVE_THE_DATE_TEMPLATE,
+        "Save the Date",
+        "Save the Date! {{ wedding.couple }}'s Wedding",
+        include_str!("../../templates/email/save_the_date.html"),
+    ),
+    (
+        super::ONE_MONTH_REMINDER_TEMPLATE,
+        "One Month Reminder",
+        "One month to go! {{ wedding.couple }}'s wedding",
+        include_str!("../../templates/email/one_month_reminder.html"),
+    ),
+    (
+        super::RSVP_DEADLINE_REMINDER_TEMPLATE,
+        "RSVP Deadline Reminder",
+        "One week left to RSVP for {{ wedding.couple }}'s wedding",
+        include_str!("../../templates/email/rsvp_deadline_reminder.html"),
+    ),
+    (
+        super::ONE_WEEK_REMINDER_TEMPLATE,
+        "One Week Reminder",
+        "One week to go! {{ wedding.couple }}'s wedding",
+        include_str!("../../templates/email/one_week_reminder.html"),
+    ),
+    (
+        super::THANK_YOU_TEMPLATE,
+        "Thank You",
+        "Thank you for celebrating with us!",
+        include_str!("../../templates/email/thank_you.html"),
+    ),
+];
+
+/// Per-invite variables, listed for the template editor.
+pub const INVITE_VARIABLES: &[TemplateVariableDoc] = &[
+    TemplateVariableDoc { name: "guest_names", description: "\"Alex\", \"Alex and Taylor\" or \"Alex, Sam, and Taylor\"" },
+    TemplateVariableDoc { name: "guests", description: "List of guests with name, first_name, email, relationship, side, age_group, tags and opt_out_url" },
+    TemplateVariableDoc { name: "invite_code", description: "The invite's unique code" },
+    TemplateVariableDoc { name: "rsvp_link", description: "Link to the invite's RSVP page" },
+    TemplateVariableDoc { name: "website_url", description: "Wedding website address" },
+    TemplateVariableDoc { name: "venue_map_url", description: "Map link for the venue" },
+    TemplateVariableDoc { name: "hotel_info_url", description: "Hotel information link" },
+    TemplateVariableDoc { name: "events", description: "Events this invite is invited to, with name, location, description, starts_at, google_calendar_url and outlook_calendar_url" },
+    TemplateVariableDoc { name: "calendar_url", description: "Download link for an .ics file with the invite's events" },
+];
+
+/// A rendered email ready to send, with its plain-text alternative.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TemplateGuest {
+    name: String,
+    first_name: String,
+    email: String,
+    relationship: String,
+    side: String,
+    age_group: String,
+    tags: Vec<String>,
+    opt_out_url: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct TemplateEvent {
+    name: String,
+    location: Option<String>,
+    description: Option<String>,
+    starts_at: Option<String>,
+    google_calendar_url: Option<Value>,
+    outlook_calendar_url: Option<Value>,
+}
+
+/// Couple names used when the `couple` variable has been deleted
+pub const DEFAULT_COUPLE: &str = "Sam & Jonah";
+
+/// The couple's names from the wedding variables.
+pub fn couple(wedding: &[EmailTemplateVariable]) -> &str {
+    wedding.iter()
+        .find(|v| v.name == "couple")
+        .map_or(DEFAULT_COUPLE, |v| v.value.as_str())
+}
+
+/// Public .ics download with an invite's events.
+pub fn calendar_download_url(api_url: &str, invite_code: &str) -> String {
+    format!("{}/api/calendar.ics?code={}", api_url.trim_end_matches('/'), invite_code)
+}
+
+/// Links shared by every email, from the sender's configuration.
+pub struct TemplateLinks<'a> {
+    pub website_url: &'a str,
+    /// Base URL of this API, for opt-out links
+    pub api_url: &'a str,
+    pub venue_map_url: &'a str,
+    pub hotel_info_url: &'a str,
+}
+
+/// Insert any built-in template that is not in the database yet. Existing
+/// templates are never overwritten. Returns the number seeded.
+pub async fn seed_default_templates(db: &PgPool) -> Result<usize, sqlx::Error> {
+    let mut seeded = 0;
+    for (template_type, name, subject, html) in DEFAULT_TEMPLATES {
+        let mut tx = db.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO email_templates (template_type, name, subject, html, version)
+             VALUES ($1, $2, $3, $4, 1)
+             ON CONFLICT (template_type) DO NOTHING"
+        )
+        .bind(template_type)
+        .bind(name)
+        .bind(subject)
+        .bind(html)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            sqlx::query(
+                "INSERT INTO email_template_versions (template_type, version, subject, html)
+                 VALUES ($1, 1, $2, $3)"
+            )
+            .bind(template_type)
+            .bind(subject)
+            .bind(html)
+            .execute(&mut *tx)
+            .await?;
+            seeded += 1;
+        }
+        tx.commit().await?;
+    }
+    Ok(seeded)
+}
+
+pub async fn load_template(db: &PgPool, template_type: &str) -> Result<Option<EmailTemplate>, sqlx::Error> {
+    sqlx::query_as::<_, EmailTemplate>("SELECT * FROM email_templates WHERE template_type = $1")
+        .bind(template_type)
+        .fetch_optional(db)
+        .await
+}
+
+pub async fn load_variables(db: &PgPool) -> Result<Vec<EmailTemplateVariable>, sqlx::Error> {
+    sqlx::query_as::<_, EmailTemplateVariable>(
+        "SELECT name, value, description FROM email_template_variables ORDER BY name"
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Variables for one invite. Links are built by us and marked safe so they
+/// are not HTML-escaped; everything guest- or admin-entered is escaped.
+/// `opt_out_tokens` maps guest ids to their opt-out link token.
+pub fn invite_context(
+    invite: &InviteWithGuests,
+    events: &[Event],
+    wedding: &[EmailTemplateVariable],
+    links: &TemplateLinks,
+    opt_out_tokens: &HashMap<Uuid, Uuid>,
+) -> Value {
+    let website_url = links.website_url.trim_end_matches('/');
+    let api_url = links.api_url.trim_end_matches('/');
+    let guest_names: Vec<String> = invite.guests.iter().map(|g| g.name.clone()).collect();
+    let guests: Vec<TemplateGuest> = invite.guests.iter()
+        .map(|g| TemplateGuest {
+            name: g.name.clone(),
+            first_name: g.name.split_whitespace().next().unwrap_or_default().to_string(),
+            email: g.email.clone(),
+            relationship: g.relationship.clone(),
+            side: g.sam_or_jonah.clone(),
+            age_group: g.age_group.clone(),
+            tags: g.tags.clone(),
+            opt_out_url: opt_out_tokens.get(&g.id)
+                .map(|token| Value::from_safe_string(format!("{}/api/email/opt-out/{}", api_url, token))),
+        })
+        .collect();
+    let couple = couple(wedding);
+    let wedding: BTreeMap<&str, &str> = wedding.iter()
+        .map(|v| (v.name.as_str(), v.value.as_str()))
+        .collect();
+    let events: Vec<TemplateEvent> = events.iter()
+        .map(|e| TemplateEvent {
+            name: e.name.clone(),
+            location: e.location.clone(),
+            description: e.description.clone(),
+            starts_at: e.starts_at
+                .and_then(|t| t.format(&time::format_description::well_known::Rfc3339).ok()),
+            google_calendar_url: calendar::google_url(e, couple).map(Value::from_safe_string),
+            outlook_calendar_url: calendar::outlook_url(e, couple).map(Value::from_safe_string),
+        })
+        .collect();
+
+    context! {
+        guest_names => super::templates::names_display(&guest_names),
+        guests => guests,
+        invite_code => invite.invite.unique_code,
+        rsvp_link => Value::from_safe_string(format!("{}/rsvp?code={}", website_url, invite.invite.unique_code)),
+        website_url => Value::from_safe_string(website_url.to_string()),
+        venue_map_url => Value::from_safe_string(links.venue_map_url.to_string()),
+        hotel_info_url => Value::from_safe_string(links.hotel_info_url.to_string()),
+        events => events,
+        calendar_url => Value::from_safe_string(calendar_download_url(api_url, &invite.invite.unique_code)),
+        wedding => wedding,
+    }
+}
+
+/// Render a subject and body. The body is HTML-escaped by default; the
+/// subject is plain text. The text part is generated from the HTML.
+pub fn render_source(subject: &str, html: &str, ctx: &Value) -> Result<RenderedEmail, minijinja::Error> {
+    let env = Environment::new();
+    let html = env.render_named_str("body.html", html, ctx)?;
+    Ok(RenderedEmail {
+        subject: env.render_named_str("subject.txt", subject, ctx)?.trim().to_string(),
+        text: super::plain_text::html_to_text(&html),
+        html,
+    })
+}
+
+/// Check that a template compiles before it is saved.
+pub fn validate_source(subject: &str, html: &str) -> Result<(), String> {
+    let env = Environment::new();
+    env.template_from_named_str("subject.txt", subject)
+        .map_err(|e| format!("Subject: {:#}", e))?;
+    env.template_from_named_str("body.html", html)
+        .map_err(|e| format!("Body: {:#}", e))?;
+    Ok(())
+}
+
+/// Variables used by a template that would render as nothing for this
+/// context: missing, null, empty text or an empty list. Loop variables are
+/// not included.
+pub fn empty_placeholders(subject: &str, html: &str, ctx: &Value) -> Vec<String> {
+    let env = Environment::new();
+    let mut names: Vec<String> = [("subject.txt", subject), ("body.html", html)]
+        .into_iter()
+        .filter_map(|(name, source)| env.template_from_named_str(name, source).ok())
+        .flat_map(|template| template.undeclared_variables(true))
+        .filter(|path| {
+            let value = path.split('.')
+                .try_fold(ctx.clone(), |value, key| value.get_attr(key))
+                .unwrap_or_default();
+            value.is_undefined() || value.is_none() || value.len() == Some(0)
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Render the current version of a template. `subject` overrides the
+/// template's own subject (campaigns carry their own) and may use variables.
+pub async fn render(
+    db: &PgPool,
+    template_type: &str,
+    subject: Option<&str>,
+    ctx: &Value,
+) -> Result<RenderedEmail, String> {
+    let template = load_template(db, template_type)
+        .await
+        .map_err(|e| format!("Failed to load email template: {}", e))?
+        .ok_or_else(|| format!("Email template '{}' not found", template_type))?;
+    let subject = subject.filter(|s| !s.trim().is_empty()).unwrap_or(&template.subject);
+
+    render_source(subject, &template.html, ctx)
+        .map_err(|e| format!("Failed to render email template '{}': {:#}", template_type, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        empty_placeholders, invite_context, render_source, validate_source, TemplateLinks,
+        DEFAULT_TEMPLATES,
+    };
+    use super::INVITATION_TEMPLATE;
+    use crate::email::ONE_MONTH_REMINDER_TEMPLATE;
+    use crate::models::{EmailTemplateVariable, Event, Guest, Invite, InviteWithGuests};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn guest(name: &str) -> Guest {
+        Guest {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            email: format!("{}@example.com", name.to_lowercase()),
+            relationship: "friend".to_string(),
+            sam_or_jonah: "sam".to_string(),
+            maybe: false,
+            unique_code: "guest123".to_string(),
+            invite_type: "double".to_string(),
+            removed: false,
+            invite_id: None,
+            postal_code: None,
+            tags: Vec::new(),
+            age_group: "adult".to_string(),
+            age: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn invite(names: &[&str]) -> InviteWithGuests {
+        InviteWithGuests {
+            invite: Invite {
+                id: Uuid::new_v4(),
+                unique_code: "ABC123".to_string(),
+                invite_type: "double".to_string(),
+                invite_sent_at: None,
+                created_at: None,
+                updated_at: None,
+            },
+            guests: names.iter().map(|name| guest(name)).collect(),
+        }
+    }
+
+    fn wedding() -> Vec<EmailTemplateVariable> {
+        [("couple", "Sam & Jonah"), ("short_date", "August 15, 2026"), ("venue", "Rouge"), ("city", "Calgary")]
+            .into_iter()
+            .map(|(name, value)| EmailTemplateVariable { name: name.to_string(), value: value.to_string(), description: None })
+            .collect()
+    }
+
+    fn default_source(template_type: &str) -> (&'static str, &'static str) {
+        let (_, _, subject, html) = DEFAULT_TEMPLATES.iter()
+            .find(|(t, ..)| *t == template_type)
+            .expect("default template exists");
+        (subject, html)
+    }
+
+    const LINKS: TemplateLinks<'static> = TemplateLinks {
+        website_url: "https://samandjonah.com/",
+        api_url: "https://api.samandjonah.com",
+        venue_map_url: "https://maps.google.com/?q=Rouge&z=15",
+        hotel_info_url: "https://samandjonah.com/travel",
+    };
+
+    #[test]
+    fn default_templates_compile() {
+        for (template_type, _, subject, html) in DEFAULT_TEMPLATES {
+            assert!(validate_source(subject, html).is_ok(), "{} does not compile", template_type);
+        }
+    }
+
+    #[test]
+    fn one_month_reminder_contains_requested_website_sections() {
+        let (subject, html) = default_source(ONE_MONTH_REMINDER_TEMPLATE);
+        let invite = invite(&["Alex", "Taylor"]);
+        let tokens = invite.guests.iter().map(|g| (g.id, Uuid::nil())).collect();
+        let ctx = invite_context(&invite, &[], &wedding(), &LINKS, &tokens);
+        let email = render_source(subject, html, &ctx).unwrap();
+
+        assert_eq!(email.subject, "One month to go! Sam & Jonah's wedding");
+        assert!(email.html.contains("Dear Alex and Taylor"));
+        assert!(email.html.contains("only one month away"));
+        assert!(email.html.contains("https://samandjonah.com/details"));
+        assert!(email.html.contains("Wedding Day Details"));
+        assert!(email.html.contains("Travel Information"));
+        assert!(email.html.contains("Things to Do in Calgary"));
+        assert!(email.html.contains("https://samandjonah.com/registry"));
+        assert!(email.html.contains("With love, Sam &amp; Jonah"));
+        assert!(!email.html.contains("samandjonah.com//"));
+        assert!(email.text.contains("Dear Alex and Taylor,"));
+        assert!(email.text.contains("Wedding Day Details (https://samandjonah.com/details)"));
+        assert!(email.html.contains(
+            "href=\"https://api.samandjonah.com/api/email/opt-out/00000000-0000-0000-0000-000000000000\""
+        ));
+        assert!(email.html.contains("Unsubscribe Taylor"));
+    }
+
+    #[test]
+    fn invitation_has_add_to_calendar_links() {
+        let (subject, html) = default_source(INVITATION_TEMPLATE);
+        let ceremony = Event {
+            id: Uuid::nil(),
+            name: "Ceremony".to_string(),
+            description: None,
+            location: Some("Rouge, 1240 8 Ave SE, Calgary, Alberta".to_string()),
+            starts_at: time::OffsetDateTime::from_unix_timestamp(1_786_831_200).ok(),
+            ends_at: None,
+            invite_all: true,
+            display_order: 1,
+            created_at: None,
+        };
+        let ctx = invite_context(&invite(&["Alex"]), &[ceremony], &wedding(), &LINKS, &HashMap::new());
+        let email = render_source(subject, html, &ctx).unwrap();
+
+        assert!(email.html.contains("Add to your calendar"));
+        assert!(email.html.contains("Ceremony: <a href=\"https://calendar.google.com/calendar/render?action=TEMPLATE&text=Sam+%26+Jonah%27s+Wedding%3A+Ceremony"));
+        assert!(email.html.contains("href=\"https://outlook.live.com/calendar/0/deeplink/compose?"));
+        assert!(email.html.contains("startdt=2026-08-15T16%3A00%3A00-06%3A00"));
+        assert!(email.html.contains("href=\"https://api.samandjonah.com/api/calendar.ics?code=ABC123\""));
+    }
+
+    #[test]
+    fn guest_text_is_escaped_but_links_are_not() {
+        let ctx = invite_context(&invite(&["<b>Alex</b>"]), &[], &wedding(), &LINKS, &HashMap::new());
+        let email = render_source(
+            "Hi {{ guests[0].first_name }}",
+            "<a href=\"{{ venue_map_url }}\">{{ guest_names }}</a> {{ rsvp_link }}",
+            &ctx,
+        )
+        .unwrap();
+
+        assert_eq!(email.subject, "Hi <b>Alex</b>");
+        assert!(email.html.contains("href=\"https://maps.google.com/?q=Rouge&z=15\""));
+        assert!(email.html.contains("&lt;b&gt;Alex&lt;&#x2f;b&gt;"));
+        assert!(email.html.contains("https://samandjonah.com/rsvp?code=ABC123"));
+    }
+
+    #[test]
+    fn flags_placeholders_that_render_empty() {
+        let ctx = invite_context(&invite(&["Alex"]), &[], &wedding(), &LINKS, &HashMap::new());
+        let empty = empty_placeholders(
+            "{{ wedding.couple }} on {{ wedding.date }}",
+            "{{ guest_names }} {{ events }} {% for g in guests %}{{ g.nickname }}{% endfor %} {{ typo }}",
+            &ctx,
+        );
+
+        assert_eq!(empty, ["events", "typo", "wedding.date"]);
+    }
+
+    #[test]
+    fn invalid_template_is_rejected() {
+        assert!(validate_source("Hello", "{% if guest_names %}unclosed").is_err());
+        assert!(validate_source("{{ guest_names", "<p></p>").is_err());
+    }
+}
@@ -12,7 +12,14 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 mod models;
 mod routes;
 mod db;
+mod dietary;
 mod email;
+mod notifications;
+mod pdf;
+mod calendar;
+mod reminders;
+mod segments;
+mod songs;
 
 #[tokio::main]
 async fn main() {
@@ -34,11 +41,7 @@ async fn main() {
 
     eprintln!("Connecting to database: {}...", &database_url[..database_url.find('@').unwrap_or(20)]);
 
-    let db = match sqlx::postgres::PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await
-    {
+    let db = match db::create_pool().await {
         Ok(pool) => {
             eprintln!("Successfully connected to database!");
             pool
@@ -97,6 +100,64 @@ async fn main() {
         }
     }
 
+    // Re-apply the newer migrations here for the same reason: sqlx may have
+    // stopped at the duplicate migration numbers. Their schema changes are
+    // idempotent, and every data change runs once under a data_seeds key.
+    eprintln!("Ensuring feature schema exists...");
+    let feature_migrations = [
+        ("007_rsvp_revisions", include_str!("../migrations/007_rsvp_revisions.sql")),
+        ("008_meal_selection", include_str!("../migrations/008_meal_selection.sql")),
+        ("009_dietary_tags", include_str!("../migrations/009_dietary_tags.sql")),
+        ("010_custom_questions", include_str!("../migrations/010_custom_questions.sql")),
+        ("011_events", include_str!("../migrations/011_events.sql")),
+        ("012_rsvp_confirmations", include_str!("../migrations/012_rsvp_confirmations.sql")),
+        ("013_rsvp_notifications", include_str!("../migrations/013_rsvp_notifications.sql")),
+        ("014_guest_lookup", include_str!("../migrations/014_guest_lookup.sql")),
+        ("015_admin_rsvps", include_str!("../migrations/015_admin_rsvps.sql")),
+        ("016_guest_tags", include_str!("../migrations/016_guest_tags.sql")),
+        ("017_song_requests", include_str!("../migrations/017_song_requests.sql")),
+        ("018_guestbook", include_str!("../migrations/018_guestbook.sql")),
+        ("019_age_groups", include_str!("../migrations/019_age_groups.sql")),
+        ("020_email_templates", include_str!("../migrations/020_email_templates.sql")),
+        ("021_audience_segments", include_str!("../migrations/021_audience_segments.sql")),
+        ("022_email_suppressions", include_str!("../migrations/022_email_suppressions.sql")),
+        ("023_reminder_campaigns", include_str!("../migrations/023_reminder_campaigns.sql")),
+        ("024_email_delivery_log", include_str!("../migrations/024_email_delivery_log.sql")),
+        ("025_email_replies", include_str!("../migrations/025_email_replies.sql")),
+        ("026_email_quota", include_str!("../migrations/026_email_quota.sql")),
+        ("027_invitation_campaign", include_str!("../migrations/027_invitation_campaign.sql")),
+    ];
+    for (name, sql) in &feature_migrations {
+        if let Err(e) = sqlx::Executor::execute(&db, *sql).await {
+            tracing::error!("Failed to apply migration {}: {}", name, e);
+            std::process::exit(1);
+        }
+    }
+
+    // Map legacy free-text dietary answers to standard codes for admin review
+    match routes::suggest_legacy_dietary_tags(&db).await {
+        Ok(0) => {}
+        Ok(count) => eprintln!("Suggested dietary codes for {} RSVPs (pending review)", count),
+        Err(e) => tracing::error!("Failed to suggest dietary codes: {}", e),
+    }
+
+    // Parse song requests saved before songs were tracked
+    match routes::backfill_song_requests(&db).await {
+        Ok(0) => {}
+        Ok(count) => eprintln!("Parsed song requests for {} guests", count),
+        Err(e) => tracing::error!("Failed to parse song requests: {}", e),
+    }
+
+    // Seed the built-in email templates so they can be edited
+    match email::template_store::seed_default_templates(&db).await {
+        Ok(0) => {}
+        Ok(count) => eprintln!("Seeded {} default email templates", count),
+        Err(e) => tracing::error!("Failed to seed email templates: {}", e),
+    }
+
+    // Daily RSVP digest for the couple
+    tokio::spawn(notifications::run_digest_scheduler(db.clone()));
+
     // Create app state
     let state = routes::AppState {
         db,
@@ -3,6 +3,8 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use rust_decimal::Decimal;
 
+use crate::dietary::DietaryOption;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Invite {
     pub id: Uuid,
@@ -32,10 +34,32 @@ pub struct Guest {
     pub invite_type: String,
     pub removed: bool,
     pub invite_id: Option<Uuid>,
+    pub postal_code: Option<String>,
+    pub tags: Vec<String>,
+    pub age_group: String,
+    pub age: Option<i32>,
     pub created_at: Option<time::OffsetDateTime>,
     pub updated_at: Option<time::OffsetDateTime>,
 }
 
+// Guest age groups
+pub const AGE_ADULT: &str = "adult";
+pub const AGE_CHILD: &str = "child";
+pub const AGE_INFANT: &str = "infant";
+pub const AGE_GROUPS: &[&str] = &[AGE_ADULT, AGE_CHILD, AGE_INFANT];
+/// Most adults allowed on one invite: the guest and their plus one
+pub const MAX_ADULTS_PER_INVITE: usize = 2;
+
+/// Age group implied by an age: under 2 is an infant, under 12 (the venue's
+/// child pricing) is a child.
+pub fn age_group_for(age: i32) -> &'static str {
+    match age {
+        a if a < 2 => AGE_INFANT,
+        a if a < 12 => AGE_CHILD,
+        _ => AGE_ADULT,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct EmailCampaign {
     pub id: Uuid,
@@ -43,12 +67,290 @@ pub struct EmailCampaign {
     pub subject: String,
     pub template_type: String,
     pub sent_count: i32,
+    /// Audience to send to; every guest when unset
+    pub segment_id: Option<Uuid>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub created_at: Option<time::OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub sent_at: Option<time::OffsetDateTime>,
 }
 
+// Suppression reasons
+pub const SUPPRESS_BOUNCE: &str = "bounce";
+pub const SUPPRESS_COMPLAINT: &str = "complaint";
+pub const SUPPRESS_MANUAL: &str = "manual";
+pub const SUPPRESS_OPT_OUT: &str = "opt_out";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailSuppression {
+    pub email: String,
+    pub reason: String,
+    pub detail: Option<String>,
+    pub guest_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSuppressionRequest {
+    pub email: String,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+/// A reply a guest sent to one of our emails
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailReply {
+    pub id: Uuid,
+    pub guest_id: Option<Uuid>,
+    pub invite_id: Option<Uuid>,
+    pub from_email: String,
+    pub from_name: Option<String>,
+    pub subject: Option<String>,
+    pub text_body: Option<String>,
+    pub html_body: Option<String>,
+    pub provider_message_id: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub received_at: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub handled_at: Option<time::OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub forwarded_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailReplyWithGuest {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub reply: EmailReply,
+    pub guest_name: Option<String>,
+}
+
+/// An already-parsed inbound email, as posted by an inbound-parse service
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InboundEmailRequest {
+    /// Sender, e.g. "Alex Smith <alex@example.com>"
+    pub from: String,
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub html: Option<String>,
+    /// Provider or Message-ID header value, used to ignore duplicates
+    #[serde(default)]
+    pub message_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplyQuery {
+    #[serde(default)]
+    pub handled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkReplyHandledRequest {
+    pub handled: bool,
+}
+
+/// Everything exchanged with a guest by email
+#[derive(Debug, Serialize)]
+pub struct GuestConversation {
+    pub guest: Guest,
+    pub replies: Vec<EmailReply>,
+    pub sent: Vec<EmailSendLogEntry>,
+}
+
+// Segment RSVP statuses
+pub const SEGMENT_ATTENDING: &str = "attending";
+pub const SEGMENT_DECLINED: &str = "declined";
+pub const SEGMENT_PENDING: &str = "pending";
+pub const SEGMENT_RSVP_STATUSES: &[&str] = &[SEGMENT_ATTENDING, SEGMENT_DECLINED, SEGMENT_PENDING];
+
+/// Guest filters for a campaign audience. Empty lists and missing values
+/// match everyone; a guest must match every filter that is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SegmentFilters {
+    /// attending, declined or pending
+    #[serde(default)]
+    pub rsvp_status: Vec<String>,
+    /// Sam, Jonah or Both (case-insensitive)
+    #[serde(default)]
+    pub sides: Vec<String>,
+    #[serde(default)]
+    pub relationships: Vec<String>,
+    /// Guests with any of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether someone on the invite has opened an email
+    #[serde(default)]
+    pub opened: Option<bool>,
+    /// Limit `opened` to one campaign's email
+    #[serde(default)]
+    pub opened_campaign_id: Option<Uuid>,
+    /// Whether the invitation has been sent to the invite
+    #[serde(default)]
+    pub invite_sent: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AudienceSegment {
+    pub id: Uuid,
+    pub name: String,
+    pub filters: sqlx::types::Json<SegmentFilters>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveSegmentRequest {
+    pub name: String,
+    #[serde(default)]
+    pub filters: SegmentFilters,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetCampaignSegmentRequest {
+    pub segment_id: Option<Uuid>,
+}
+
+/// How many would receive an email
+#[derive(Debug, Serialize)]
+pub struct AudienceCount {
+    pub invites: usize,
+    pub guests: usize,
+    /// Guests with a usable email address
+    pub recipients: usize,
+}
+
+/// This month's email quota with the provider
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailQuota {
+    /// e.g. "2026-10"
+    pub month: String,
+    pub limit: i64,
+    pub used: i64,
+    pub remaining: i64,
+}
+
+/// A campaign's audience and whether sending to it fits this month's quota
+#[derive(Debug, Serialize)]
+pub struct CampaignAudience {
+    #[serde(flatten)]
+    pub count: AudienceCount,
+    pub quota: EmailQuota,
+    /// Why the send would be refused, or a low-quota warning
+    pub quota_warning: Option<String>,
+    pub exceeds_quota: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CampaignPreviewQuery {
+    /// Invite id or code to render for; defaults to the first invite
+    pub invite: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TestSendRequest {
+    /// Addresses to send to instead of the invite's guests
+    pub to: Vec<String>,
+    /// Invite id or code to render for; defaults to the first invite
+    #[serde(default)]
+    pub invite: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestSendResponse {
+    pub template_type: String,
+    pub invite_code: String,
+    pub subject: String,
+    pub to: Vec<String>,
+}
+
+/// A campaign email rendered for one invite, as it would be sent
+#[derive(Debug, Serialize)]
+pub struct CampaignPreview {
+    pub campaign_id: Uuid,
+    pub template_type: String,
+    pub invite_id: Uuid,
+    pub invite_code: String,
+    pub recipients: Vec<String>,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+    /// Template variables that render empty for this invite
+    pub empty_placeholders: Vec<String>,
+}
+
+/// Editable email template (MiniJinja source) at its current version
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailTemplate {
+    pub template_type: String,
+    pub name: String,
+    pub subject: String,
+    pub html: String,
+    pub version: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailTemplateVersion {
+    pub id: Uuid,
+    pub template_type: String,
+    pub version: i32,
+    pub subject: String,
+    pub html: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailTemplateWithHistory {
+    #[serde(flatten)]
+    pub template: EmailTemplate,
+    pub versions: Vec<EmailTemplateVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveEmailTemplateRequest {
+    /// Required when creating a new template
+    #[serde(default)]
+    pub name: Option<String>,
+    pub subject: String,
+    pub html: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollbackEmailTemplateRequest {
+    pub version: i32,
+}
+
+/// Wedding-wide value available to templates as `{{ wedding.<name> }}`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailTemplateVariable {
+    pub name: String,
+    pub value: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailTemplateVariables {
+    /// Per-invite variables filled in when an email is rendered
+    pub invite: Vec<TemplateVariableDoc>,
+    pub wedding: Vec<EmailTemplateVariable>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateVariableDoc {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct EmailSend {
     pub id: Uuid,
@@ -59,6 +361,36 @@ pub struct EmailSend {
     pub opened_at: Option<time::OffsetDateTime>,
     pub opened_count: i32,
     pub reminder_key: Option<String>,
+    /// Message id returned by Resend
+    pub provider_message_id: Option<String>,
+    pub recipients: Vec<String>,
+    pub subject: Option<String>,
+    pub template_type: Option<String>,
+    pub status: String,
+    pub status_updated_at: Option<time::OffsetDateTime>,
+}
+
+// Delivery statuses, in the order they can advance
+pub const DELIVERY_SENT: &str = "sent";
+pub const DELIVERY_DELAYED: &str = "delivery_delayed";
+pub const DELIVERY_DELIVERED: &str = "delivered";
+pub const DELIVERY_BOUNCED: &str = "bounced";
+pub const DELIVERY_COMPLAINED: &str = "complained";
+pub const DELIVERY_STATUSES: &[&str] = &[
+    DELIVERY_SENT,
+    DELIVERY_DELAYED,
+    DELIVERY_DELIVERED,
+    DELIVERY_BOUNCED,
+    DELIVERY_COMPLAINED,
+];
+
+/// A delivery log entry with the campaign it belongs to, if any
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EmailSendLogEntry {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub send: EmailSend,
+    pub campaign_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -68,12 +400,56 @@ pub struct Rsvp {
     pub invite_id: Option<Uuid>,
     pub attending: bool,
     pub dietary_restrictions: Option<String>,
+    pub dietary_tags: Vec<String>,
+    pub dietary_review_status: Option<String>,
     pub song_requests: Option<String>,
     pub message: Option<String>,
+    pub source: String,
+    pub entered_by: Option<String>,
     pub submitted_at: Option<time::OffsetDateTime>,
     pub updated_at: Option<time::OffsetDateTime>,
 }
 
+// RSVP revision sources
+pub const RSVP_SOURCE_GUEST_LINK: &str = "guest_link";
+pub const RSVP_SOURCE_PHONE: &str = "phone";
+pub const RSVP_SOURCE_PAPER: &str = "paper";
+pub const RSVP_SOURCE_IN_PERSON: &str = "in_person";
+/// Channels an admin can record when entering an RSVP for a guest
+pub const ADMIN_RSVP_CHANNELS: &[&str] = &[RSVP_SOURCE_PHONE, RSVP_SOURCE_PAPER, RSVP_SOURCE_IN_PERSON];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RsvpFieldChange {
+    pub field: String,
+    pub from: Option<serde_json::Value>,
+    pub to: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RsvpRevision {
+    pub id: Uuid,
+    pub rsvp_id: Option<Uuid>,
+    pub guest_id: Uuid,
+    pub invite_id: Option<Uuid>,
+    pub attending: bool,
+    pub dietary_restrictions: Option<String>,
+    pub dietary_tags: Vec<String>,
+    pub song_requests: Option<String>,
+    pub message: Option<String>,
+    pub source: String,
+    pub entered_by: Option<String>,
+    pub changes: sqlx::types::Json<Vec<RsvpFieldChange>>,
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RsvpRevisionWithGuest {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub revision: RsvpRevision,
+    pub guest_name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RsvpWithGuest {
     #[serde(flatten)]
@@ -89,19 +465,86 @@ pub struct RsvpStats {
     pub total_attending: i64,
     pub total_declined: i64,
     pub total_pending: i64,
+    pub events: Vec<EventStats>,
+    pub by_age_group: Vec<AgeGroupStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AgeGroupStats {
+    pub age_group: String,
+    pub invited: i64,
+    pub attending: i64,
+    pub declined: i64,
+    pub pending: i64,
 }
 
+/// RSVP entered by an admin on a guest's behalf
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GuestRsvpRequest {
-    pub attending: bool,
-    pub dietary_restrictions: Option<String>,
-    pub song_requests: Option<String>,
-    pub message: Option<String>,
+pub struct AdminRsvpSubmission {
+    pub channel: String,
+    pub entered_by: String,
+    #[serde(flatten)]
+    pub submission: InviteRsvpSubmission,
+}
+
+/// Public lookup for guests who lost their invite code
+#[derive(Debug, Clone, Deserialize)]
+pub struct FindInviteRequest {
+    pub name: String,
+    pub email: Option<String>,
+    pub postal_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FindInviteResponse {
+    pub code: String,
+}
+
+// RSVP analytics types
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RsvpBreakdownRow {
+    pub key: String,
+    pub invited: i64,
+    pub responded: i64,
+    pub attending: i64,
+    pub declined: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct DailyResponses {
+    pub day: time::Date,
+    pub responded: i64,
+    pub cumulative_responded: i64,
+    pub cumulative_attending: i64,
+    pub cumulative_declined: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OpenedNotResponded {
+    pub invite_id: Uuid,
+    pub unique_code: String,
+    pub guest_names: Vec<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_opened_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RsvpAnalytics {
+    pub by_side: Vec<RsvpBreakdownRow>,
+    pub by_relationship: Vec<RsvpBreakdownRow>,
+    pub by_invite_type: Vec<RsvpBreakdownRow>,
+    pub by_tag: Vec<RsvpBreakdownRow>,
+    pub daily: Vec<DailyResponses>,
+    pub average_days_to_respond: Option<f64>,
+    pub opened_not_responded: Vec<OpenedNotResponded>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InviteRsvpSubmission {
     pub guests: Vec<GuestRsvpEntry>,
+    /// Answers to invite-scoped questions
+    #[serde(default)]
+    pub answers: Option<Vec<QuestionAnswer>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,15 +552,233 @@ pub struct GuestRsvpEntry {
     pub guest_id: Uuid,
     pub attending: bool,
     pub dietary_restrictions: Option<String>,
+    /// Standard allergen/diet codes; when omitted they are suggested from
+    /// the free-text restrictions and flagged for admin review
+    #[serde(default)]
+    pub dietary_tags: Option<Vec<String>>,
     pub song_requests: Option<String>,
     pub message: Option<String>,
+    /// Omitted selections leave any previous choices unchanged
+    #[serde(default)]
+    pub meal_selections: Option<Vec<MealSelection>>,
+    /// Answers to guest-scoped questions; omitted answers are left unchanged
+    #[serde(default)]
+    pub answers: Option<Vec<QuestionAnswer>>,
+    /// Attendance per event the invite was asked to; omitted responses are
+    /// left unchanged, and declining the wedding declines every event
+    #[serde(default)]
+    pub events: Option<Vec<EventAttendance>>,
+    /// Opt in (or out) of showing this RSVP's message in the guestbook
+    #[serde(default)]
+    pub publish_message: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct InviteRsvpResponse {
     pub invite: InviteWithGuests,
     pub rsvps: Vec<Rsvp>,
     pub already_responded: bool,
+    pub menu: Vec<CourseWithOptions>,
+    pub meal_selections: Vec<GuestMealSelection>,
+    pub dietary_options: &'static [DietaryOption],
+    pub questions: Vec<RsvpQuestion>,
+    pub answers: Vec<RsvpAnswer>,
+    pub events: Vec<Event>,
+    pub event_responses: Vec<GuestEventResponse>,
+}
+
+// Event types
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Event {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub starts_at: Option<time::OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub ends_at: Option<time::OffsetDateTime>,
+    pub invite_all: bool,
+    pub display_order: i32,
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateEventRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub starts_at: Option<time::OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub ends_at: Option<time::OffsetDateTime>,
+    pub invite_all: Option<bool>,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetEventInvitesRequest {
+    pub invite_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventAttendance {
+    pub event_id: Uuid,
+    pub attending: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GuestEventResponse {
+    pub guest_id: Uuid,
+    pub event_id: Uuid,
+    pub attending: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EventStats {
+    pub event_id: Uuid,
+    pub name: String,
+    pub invited: i64,
+    pub attending: i64,
+    pub declined: i64,
+    pub pending: i64,
+}
+
+// Custom RSVP question types
+pub const QUESTION_SCOPE_GUEST: &str = "guest";
+pub const QUESTION_SCOPE_INVITE: &str = "invite";
+pub const QUESTION_TYPES: &[&str] = &["yes_no", "single_choice", "multi_choice", "text", "number"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RsvpQuestion {
+    pub id: Uuid,
+    pub prompt: String,
+    pub question_type: String,
+    pub scope: String,
+    pub required: bool,
+    pub choices: Vec<String>,
+    pub display_order: i32,
+    pub active: bool,
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRsvpQuestionRequest {
+    pub prompt: String,
+    pub question_type: String,
+    pub scope: Option<String>,
+    pub required: Option<bool>,
+    pub choices: Option<Vec<String>>,
+    pub display_order: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RsvpAnswer {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub invite_id: Uuid,
+    pub guest_id: Option<Uuid>,
+    pub value: serde_json::Value,
+    pub created_at: Option<time::OffsetDateTime>,
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionAnswer {
+    pub question_id: Uuid,
+    pub value: serde_json::Value,
+}
+
+// Menu and meal selection types
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MenuCourse {
+    pub id: Uuid,
+    pub name: String,
+    pub required: bool,
+    pub display_order: i32,
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MenuOption {
+    pub id: Uuid,
+    pub course_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_kids_meal: bool,
+    pub display_order: i32,
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseWithOptions {
+    #[serde(flatten)]
+    pub course: MenuCourse,
+    pub options: Vec<MenuOption>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealSelection {
+    pub course_id: Uuid,
+    pub option_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GuestMealSelection {
+    pub guest_id: Uuid,
+    pub course_id: Uuid,
+    pub option_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMenuCourseRequest {
+    pub name: String,
+    pub required: Option<bool>,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMenuOptionRequest {
+    pub course_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_kids_meal: Option<bool>,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MealOptionTotal {
+    pub course_id: Uuid,
+    pub course_name: String,
+    pub option_id: Uuid,
+    pub option_name: String,
+    pub is_kids_meal: bool,
+    pub count: i64,
+    pub adult_count: i64,
+    pub child_count: i64,
+    pub infant_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GuestMealRow {
+    pub guest_id: Uuid,
+    pub guest_name: String,
+    pub age_group: String,
+    pub unique_code: Option<String>,
+    pub course_name: Option<String>,
+    pub option_name: Option<String>,
+    pub is_kids_meal: Option<bool>,
+    pub dietary_restrictions: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatererReport {
+    pub attending_guests: i64,
+    pub attending_by_age_group: Vec<AgeGroupStats>,
+    pub missing_selections: i64,
+    pub totals: Vec<MealOptionTotal>,
+    pub guests: Vec<GuestMealRow>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,6 +786,39 @@ pub struct AdminRsvpEntry {
     pub invite: InviteWithGuests,
     pub rsvps: Vec<RsvpWithGuest>,
     pub status: String, // "attending", "declined", "partial", "pending"
+    pub changed_mind_at: Option<time::OffsetDateTime>,
+}
+
+// Dietary report types
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DietaryGuest {
+    pub rsvp_id: Uuid,
+    pub guest_id: Uuid,
+    pub guest_name: String,
+    pub attending: bool,
+    pub dietary_tags: Vec<String>,
+    pub dietary_restrictions: Option<String>,
+    pub dietary_review_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DietaryGroup {
+    pub code: &'static str,
+    pub label: &'static str,
+    pub kind: &'static str,
+    pub guests: Vec<DietaryGuest>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DietaryReport {
+    pub groups: Vec<DietaryGroup>,
+    pub notes_only: Vec<DietaryGuest>,
+    pub pending_review: Vec<DietaryGuest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDietaryTagsRequest {
+    pub dietary_tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -137,6 +831,12 @@ pub struct SendInvitationResponse {
     pub success: bool,
     pub sent_count: usize,
     pub errors: Vec<String>,
+    /// Addresses skipped because they are on the suppression list
+    #[serde(default)]
+    pub suppressed: Vec<String>,
+    /// Set when this month's email quota is nearly used up
+    #[serde(default)]
+    pub quota_warning: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -177,6 +877,128 @@ pub struct RegistryContribution {
     pub created_at: Option<time::OffsetDateTime>,
 }
 
+// RSVP notification types
+pub const NOTIFY_OFF: &str = "off";
+pub const NOTIFY_IMMEDIATE: &str = "immediate";
+pub const NOTIFY_DAILY: &str = "daily";
+pub const NOTIFICATION_FREQUENCIES: &[&str] = &[NOTIFY_OFF, NOTIFY_IMMEDIATE, NOTIFY_DAILY];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationSettings {
+    pub recipients: Vec<String>,
+    pub frequency: String,
+    pub digest_hour_utc: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_digest_at: Option<time::OffsetDateTime>,
+    pub updated_at: Option<time::OffsetDateTime>,
+    /// Forward guest replies to the recipients as they arrive
+    pub forward_replies: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNotificationSettingsRequest {
+    pub recipients: Vec<String>,
+    pub frequency: String,
+    pub digest_hour_utc: Option<i32>,
+    #[serde(default)]
+    pub forward_replies: Option<bool>,
+}
+
+/// RSVP activity since the last notification, with current totals
+#[derive(Debug, Clone, Serialize)]
+pub struct RsvpDigest {
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub since: Option<time::OffsetDateTime>,
+    pub accepted: Vec<String>,
+    pub declined: Vec<String>,
+    pub changed: Vec<String>,
+    pub messages: Vec<String>,
+    pub stats: RsvpStats,
+}
+
+// Song request types
+pub const SONG_PENDING: &str = "pending";
+pub const SONG_APPROVED: &str = "approved";
+pub const SONG_BANNED: &str = "banned";
+pub const SONG_STATUSES: &[&str] = &[SONG_PENDING, SONG_APPROVED, SONG_BANNED];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Song {
+    pub id: Uuid,
+    pub artist: Option<String>,
+    pub title: String,
+    pub status: String,
+    pub created_at: Option<time::OffsetDateTime>,
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SongWithRequests {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub song: Song,
+    pub request_count: i64,
+    pub requested_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSongStatusRequest {
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaylistShareLink {
+    pub token: String,
+    pub url: String,
+}
+
+// Guestbook types
+pub const GUESTBOOK_PENDING: &str = "pending";
+pub const GUESTBOOK_APPROVED: &str = "approved";
+pub const GUESTBOOK_REJECTED: &str = "rejected";
+pub const GUESTBOOK_STATUSES: &[&str] = &[GUESTBOOK_PENDING, GUESTBOOK_APPROVED, GUESTBOOK_REJECTED];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GuestbookEntry {
+    pub id: Uuid,
+    pub invite_id: Option<Uuid>,
+    pub guest_id: Option<Uuid>,
+    pub rsvp_id: Option<Uuid>,
+    pub author_name: String,
+    pub message: String,
+    pub status: String,
+    pub reviewed_at: Option<time::OffsetDateTime>,
+    pub created_at: Option<time::OffsetDateTime>,
+    pub updated_at: Option<time::OffsetDateTime>,
+}
+
+/// What the public wall shows for an approved entry
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct PublicGuestbookEntry {
+    pub author_name: String,
+    pub message: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateGuestbookPostRequest {
+    pub code: String,
+    pub guest_id: Option<Uuid>,
+    pub author_name: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuestbookQuery {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateGuestbookStatusRequest {
+    pub status: String,
+}
+
 // Registry request/response types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateCategoryRequest {
@@ -0,0 +1,341 @@
+// RSVP notifications and the daily digest for the couple
+
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime, Time};
+use uuid::Uuid;
+
+use crate::email::EmailService;
+use crate::models::{
+    EmailReply, NotificationSettings, RsvpDigest, RsvpRevisionWithGuest, RsvpStats, NOTIFY_DAILY,
+    NOTIFY_IMMEDIATE,
+};
+
+/// How often the scheduler checks whether the daily digest is due
+const DIGEST_CHECK_INTERVAL_SECS: u64 = 10 * 60;
+
+pub async fn load_settings(db: &PgPool) -> Result<NotificationSettings, sqlx::Error> {
+    sqlx::query_as::<_, NotificationSettings>(
+        "SELECT recipients, frequency, digest_hour_utc, last_digest_at, updated_at, forward_replies
+         FROM notification_settings"
+    )
+    .fetch_one(db)
+    .await
+}
+
+fn email_service(db: &PgPool) -> Option<EmailService> {
+    let resend_api_key = std::env::var("RESEND_API_KEY").ok()?;
+    Some(crate::routes::configured_email_service(db, resend_api_key))
+}
+
+/// Sort revisions into first-time accepts and declines, changed answers and
+/// new messages. Revisions are expected oldest first.
+pub fn build_digest(
+    revisions: &[RsvpRevisionWithGuest],
+    stats: RsvpStats,
+    since: Option<OffsetDateTime>,
+) -> RsvpDigest {
+    let mut digest = RsvpDigest {
+        since,
+        accepted: Vec::new(),
+        declined: Vec::new(),
+        changed: Vec::new(),
+        messages: Vec::new(),
+        stats,
+    };
+
+    for entry in revisions {
+        let name = &entry.guest_name;
+        let revision = &entry.revision;
+        let changes = &revision.changes.0;
+        let first_response = changes.iter().any(|c| c.field == "attending" && c.from.is_none());
+
+        if first_response {
+            if revision.attending {
+                digest.accepted.push(name.clone());
+            } else {
+                digest.declined.push(name.clone());
+            }
+        } else {
+            let fields: Vec<String> = changes.iter()
+                .filter(|c| c.field != "message")
+                .map(|c| match c.field.as_str() {
+                    "attending" if revision.attending => "now attending".to_string(),
+                    "attending" => "now declining".to_string(),
+                    field => field.replace('_', " "),
+                })
+                .collect();
+            if !fields.is_empty() {
+                digest.changed.push(format!("{}: {}", name, fields.join(", ")));
+            }
+        }
+
+        let message = changes.iter()
+            .find(|c| c.field == "message")
+            .and_then(|c| c.to.as_ref())
+            .and_then(|v| v.as_str());
+        if let Some(message) = message {
+            digest.messages.push(format!("{}: {}", name, message));
+        }
+    }
+
+    digest
+}
+
+/// Whether today's digest is due: the configured hour has passed and no
+/// digest has gone out since then.
+fn digest_due(now: OffsetDateTime, digest_hour_utc: i32, last_digest_at: Option<OffsetDateTime>) -> bool {
+    let Ok(hour) = Time::from_hms(digest_hour_utc.clamp(0, 23) as u8, 0, 0) else {
+        return false;
+    };
+    let scheduled = now.replace_time(hour);
+    now >= scheduled && last_digest_at.is_none_or(|last| last < scheduled)
+}
+
+/// Activity since the last digest (or the past day), without sending it.
+pub async fn pending_digest(db: &PgPool, settings: &NotificationSettings) -> Result<RsvpDigest, String> {
+    let since = settings.last_digest_at
+        .unwrap_or_else(|| OffsetDateTime::now_utc() - Duration::days(1));
+
+    let revisions = sqlx::query_as::<_, RsvpRevisionWithGuest>(
+        "SELECT rr.*, g.name AS guest_name
+         FROM rsvp_revisions rr
+         INNER JOIN guests g ON g.id = rr.guest_id
+         WHERE rr.created_at > $1 AND rr.changes <> '[]'::jsonb
+         ORDER BY rr.created_at, g.name"
+    )
+    .bind(since)
+    .fetch_all(db)
+    .await
+    .map_err(|e| format!("Failed to fetch RSVP revisions: {}", e))?;
+
+    let stats = crate::routes::load_rsvp_stats(db)
+        .await
+        .map_err(|e| format!("Failed to fetch RSVP stats: {}", e))?;
+
+    Ok(build_digest(&revisions, stats, Some(since)))
+}
+
+/// Send the digest of everything since the last one and move the marker
+/// forward. Nothing is emailed when there was no activity. Returns the
+/// number of updates included.
+pub async fn send_digest(db: &PgPool, settings: &NotificationSettings) -> Result<usize, String> {
+    let now = OffsetDateTime::now_utc();
+    let digest = pending_digest(db, settings).await?;
+    let updates = digest.accepted.len() + digest.declined.len() + digest.changed.len() + digest.messages.len();
+
+    if updates > 0 {
+        if settings.recipients.is_empty() {
+            return Err("No notification recipients configured".to_string());
+        }
+        let service = email_service(db).ok_or("RESEND_API_KEY environment variable not set")?;
+        let subject = format!("RSVP digest: {} update{}", updates, if updates == 1 { "" } else { "s" });
+        service.send_rsvp_digest(&settings.recipients, &subject, &digest).await?;
+    }
+
+    sqlx::query("UPDATE notification_settings SET last_digest_at = $1")
+        .bind(now)
+        .execute(db)
+        .await
+        .map_err(|e| format!("Failed to record digest: {}", e))?;
+
+    Ok(updates)
+}
+
+/// Background loop that sends the daily digest when it is due.
+pub async fn run_digest_scheduler(db: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(DIGEST_CHECK_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+
+        let settings = match load_settings(&db).await {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::error!("Failed to load notification settings: {}", e);
+                continue;
+            }
+        };
+        if settings.frequency != NOTIFY_DAILY
+            || settings.recipients.is_empty()
+            || !digest_due(OffsetDateTime::now_utc(), settings.digest_hour_utc, settings.last_digest_at)
+        {
+            continue;
+        }
+
+        if let Err(e) = send_digest(&db, &settings).await {
+            tracing::error!("Failed to send RSVP digest: {}", e);
+        }
+    }
+}
+
+/// Email the couple straight away about the latest submission for an invite
+/// when notifications are set to immediate.
+pub async fn notify_rsvp_change(db: PgPool, invite_id: Uuid) {
+    let settings = match load_settings(&db).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::error!("Failed to load notification settings: {}", e);
+            return;
+        }
+    };
+    if settings.frequency != NOTIFY_IMMEDIATE || settings.recipients.is_empty() {
+        return;
+    }
+    let Some(service) = email_service(&db) else {
+        tracing::warn!("RESEND_API_KEY not set; skipping RSVP notification");
+        return;
+    };
+
+    // Revisions from one submission share the transaction timestamp
+    let revisions = sqlx::query_as::<_, RsvpRevisionWithGuest>(
+        "SELECT rr.*, g.name AS guest_name
+         FROM rsvp_revisions rr
+         INNER JOIN guests g ON g.id = rr.guest_id
+         WHERE rr.invite_id = $1
+           AND rr.changes <> '[]'::jsonb
+           AND rr.created_at = (SELECT MAX(created_at) FROM rsvp_revisions WHERE invite_id = $1)
+         ORDER BY g.name"
+    )
+    .bind(invite_id)
+    .fetch_all(&db)
+    .await;
+    let stats = crate::routes::load_rsvp_stats(&db).await;
+
+    let (revisions, stats) = match (revisions, stats) {
+        (Ok(revisions), Ok(stats)) => (revisions, stats),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to build RSVP notification: {}", e);
+            return;
+        }
+    };
+    if revisions.is_empty() {
+        return;
+    }
+
+    let names: Vec<&str> = revisions.iter().map(|r| r.guest_name.as_str()).collect();
+    let subject = format!("RSVP update from {}", names.join(", "));
+    let digest = build_digest(&revisions, stats, None);
+    if let Err(e) = service.send_rsvp_digest(&settings.recipients, &subject, &digest).await {
+        tracing::error!("Failed to send RSVP notification: {}", e);
+    }
+}
+
+/// Forward a guest's reply to the notification recipients when reply
+/// forwarding is on, and record when it was forwarded.
+pub async fn forward_reply(db: PgPool, reply: EmailReply) {
+    let settings = match load_settings(&db).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::error!("Failed to load notification settings: {}", e);
+            return;
+        }
+    };
+    if !settings.forward_replies || settings.recipients.is_empty() {
+        return;
+    }
+    let Some(service) = email_service(&db) else {
+        tracing::warn!("RESEND_API_KEY not set; skipping reply forward");
+        return;
+    };
+
+    let guest_name = match reply.guest_id {
+        Some(guest_id) => sqlx::query_scalar::<_, String>("SELECT name FROM guests WHERE id = $1")
+            .bind(guest_id)
+            .fetch_optional(&db)
+            .await
+            .unwrap_or_default(),
+        None => None,
+    };
+    if let Err(e) = service.send_reply_forward(&settings.recipients, &reply, guest_name.as_deref()).await {
+        tracing::error!("Failed to forward reply {}: {}", reply.id, e);
+        return;
+    }
+
+    if let Err(e) = sqlx::query("UPDATE email_replies SET forwarded_at = NOW() WHERE id = $1")
+        .bind(reply.id)
+        .execute(&db)
+        .await
+    {
+        tracing::error!("Failed to record reply forward: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_digest, digest_due};
+    use crate::models::{RsvpFieldChange, RsvpRevision, RsvpRevisionWithGuest, RsvpStats};
+    use serde_json::json;
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
+    use uuid::Uuid;
+
+    fn at(timestamp: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(timestamp, &Rfc3339).unwrap()
+    }
+
+    fn revision(name: &str, attending: bool, changes: Vec<RsvpFieldChange>) -> RsvpRevisionWithGuest {
+        RsvpRevisionWithGuest {
+            revision: RsvpRevision {
+                id: Uuid::new_v4(),
+                rsvp_id: None,
+                guest_id: Uuid::new_v4(),
+                invite_id: None,
+                attending,
+                dietary_restrictions: None,
+                dietary_tags: Vec::new(),
+                song_requests: None,
+                message: None,
+                source: "guest_link".to_string(),
+                entered_by: None,
+                changes: sqlx::types::Json(changes),
+                created_at: None,
+            },
+            guest_name: name.to_string(),
+        }
+    }
+
+    fn change(field: &str, from: Option<serde_json::Value>, to: Option<serde_json::Value>) -> RsvpFieldChange {
+        RsvpFieldChange { field: field.to_string(), from, to }
+    }
+
+    #[test]
+    fn digest_separates_new_responses_changes_and_messages() {
+        let stats = RsvpStats {
+            total_invited: 4,
+            total_responded: 3,
+            total_attending: 2,
+            total_declined: 1,
+            total_pending: 1,
+            events: Vec::new(),
+            by_age_group: Vec::new(),
+        };
+        let revisions = [
+            revision("Alex", true, vec![
+                change("attending", None, Some(json!(true))),
+                change("message", None, Some(json!("Can't wait!"))),
+            ]),
+            revision("Taylor", false, vec![change("attending", None, Some(json!(false)))]),
+            revision("Sam", false, vec![
+                change("attending", Some(json!(true)), Some(json!(false))),
+                change("meal_selections", Some(json!(["Main: Beef"])), None),
+            ]),
+        ];
+
+        let digest = build_digest(&revisions, stats, None);
+
+        assert_eq!(digest.accepted, ["Alex"]);
+        assert_eq!(digest.declined, ["Taylor"]);
+        assert_eq!(digest.changed, ["Sam: now declining, meal selections"]);
+        assert_eq!(digest.messages, ["Alex: Can't wait!"]);
+    }
+
+    #[test]
+    fn digest_is_due_once_per_day_after_the_configured_hour() {
+        let morning = at("2026-05-01T13:00:00Z");
+        let afternoon = at("2026-05-01T15:00:00Z");
+
+        assert!(!digest_due(morning, 14, None));
+        assert!(digest_due(afternoon, 14, None));
+        assert!(digest_due(afternoon, 14, Some(at("2026-04-30T14:05:00Z"))));
+        assert!(!digest_due(afternoon, 14, Some(at("2026-05-01T14:05:00Z"))));
+    }
+}
@@ -0,0 +1,130 @@
+// Minimal text-only PDF writer for admin reports and exports
+
+const PAGE_WIDTH: f32 = 612.0; // US Letter, in points
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 54.0;
+const TITLE_SIZE: f32 = 16.0;
+const BODY_SIZE: f32 = 10.0;
+const LINE_HEIGHT: f32 = 14.0;
+const MAX_LINE_CHARS: usize = 95;
+
+/// Render a title and lines of text as a paginated PDF using Helvetica.
+/// Long lines are wrapped; characters outside Latin-1 are replaced with `?`.
+pub fn text_document(title: &str, lines: &[String]) -> Vec<u8> {
+    let lines_per_page = ((PAGE_HEIGHT - 2.0 * MARGIN - 2.0 * LINE_HEIGHT) / LINE_HEIGHT) as usize;
+    let wrapped: Vec<String> = lines.iter().flat_map(|l| wrap(l, MAX_LINE_CHARS)).collect();
+    let mut pages: Vec<&[String]> = wrapped.chunks(lines_per_page.max(1)).collect();
+    if pages.is_empty() {
+        pages.push(&[]);
+    }
+
+    // Objects: 1 catalog, 2 page tree, 3 font, then a page and content stream per page
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + i * 2).collect();
+    objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+    objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()).into_bytes());
+    objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec());
+
+    for (index, page_lines) in pages.iter().enumerate() {
+        let mut content = Vec::new();
+        let mut y = PAGE_HEIGHT - MARGIN;
+        if index == 0 {
+            content.extend(text_op(title, TITLE_SIZE, y));
+            y -= 2.0 * LINE_HEIGHT;
+        }
+        for line in page_lines.iter() {
+            content.extend(text_op(line, BODY_SIZE, y));
+            y -= LINE_HEIGHT;
+        }
+        content.extend(text_op(&format!("Page {} of {}", index + 1, pages.len()), 8.0, MARGIN / 2.0));
+
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT, page_ids[index] + 1
+        ).into_bytes());
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        out.extend(object);
+        out.extend(b"\nendobj\n");
+    }
+    let xref_offset = out.len();
+    out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        out.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    out.extend(format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ).into_bytes());
+    out
+}
+
+fn text_op(text: &str, size: f32, y: f32) -> Vec<u8> {
+    let mut op = format!("BT /F1 {} Tf {} {} Td (", size, MARGIN, y).into_bytes();
+    for ch in text.chars() {
+        match ch {
+            '(' | ')' | '\\' => {
+                op.push(b'\\');
+                op.push(ch as u8);
+            }
+            ' '..='~' => op.push(ch as u8),
+            '\u{a0}'..='\u{ff}' => op.extend(format!("\\{:03o}", ch as u32).into_bytes()),
+            '\u{2018}' | '\u{2019}' => op.push(b'\''),
+            '\u{201c}' | '\u{201d}' => op.push(b'"'),
+            '\u{2013}' | '\u{2014}' => op.push(b'-'),
+            '\t' => op.push(b' '),
+            _ => op.push(b'?'),
+        }
+    }
+    op.extend(b") Tj ET\n");
+    op
+}
+
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut wrapped = Vec::new();
+    for paragraph in line.split('\n') {
+        let mut current = String::new();
+        for word in paragraph.split(' ') {
+            if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+                wrapped.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        wrapped.push(current);
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::text_document;
+
+    #[test]
+    fn text_document_is_well_formed_and_paginates() {
+        let lines: Vec<String> = (0..120).map(|i| format!("Guest ({}) \u{e9}", i)).collect();
+        let pdf = text_document("Caterer Report", &lines);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("(Guest \\(0\\) \\351) Tj"));
+
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(text[startxref..].starts_with("xref"));
+    }
+}
@@ -0,0 +1,177 @@
+// Reminder emails: who each reminder goes to and how its sends stay idempotent
+
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::email::{
+    ONE_MONTH_REMINDER_TEMPLATE, ONE_WEEK_REMINDER_TEMPLATE, RSVP_DEADLINE_REMINDER_TEMPLATE,
+    THANK_YOU_TEMPLATE,
+};
+use crate::models::{EmailCampaign, Guest, InviteWithGuests, SegmentFilters, SEGMENT_ATTENDING, SEGMENT_PENDING};
+use crate::segments;
+
+/// The guests a reminder is addressed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderAudience {
+    /// Guests who RSVP'd yes. Declined guests on the same invite are left off.
+    Attending,
+    /// Guests who were sent an invitation and have not responded
+    AwaitingRsvp,
+}
+
+impl ReminderAudience {
+    pub fn filters(self) -> SegmentFilters {
+        match self {
+            ReminderAudience::Attending => SegmentFilters {
+                rsvp_status: vec![SEGMENT_ATTENDING.to_string()],
+                ..SegmentFilters::default()
+            },
+            ReminderAudience::AwaitingRsvp => SegmentFilters {
+                rsvp_status: vec![SEGMENT_PENDING.to_string()],
+                invite_sent: Some(true),
+                ..SegmentFilters::default()
+            },
+        }
+    }
+}
+
+/// A reminder that is sent at most once to each invite.
+#[derive(Debug, Serialize)]
+pub struct Reminder {
+    /// Key used in admin URLs, e.g. `/reminders/one-month/send`
+    pub key: &'static str,
+    /// Stored template the reminder renders; also identifies its campaign
+    pub template_type: &'static str,
+    /// Name of the reminder's campaign
+    pub name: &'static str,
+    /// Subject of the reminder's campaign when it is first created
+    pub subject: &'static str,
+    /// When to send it
+    pub description: &'static str,
+    pub audience: ReminderAudience,
+    /// Whether the invite's events are attached as an .ics file
+    pub attach_calendar: bool,
+}
+
+pub const REMINDERS: &[Reminder] = &[
+    Reminder {
+        key: "rsvp-deadline",
+        template_type: RSVP_DEADLINE_REMINDER_TEMPLATE,
+        name: "RSVP Deadline Reminder",
+        subject: "One week left to RSVP for Sam & Jonah's wedding",
+        description: "Seven days before the RSVP deadline, to invited guests who have not responded",
+        audience: ReminderAudience::AwaitingRsvp,
+        attach_calendar: true,
+    },
+    Reminder {
+        key: "one-month",
+        template_type: ONE_MONTH_REMINDER_TEMPLATE,
+        name: "One Month Reminder - July 2026",
+        subject: "One month to go! Sam & Jonah's wedding",
+        description: "One month before the wedding, to confirmed attendees",
+        audience: ReminderAudience::Attending,
+        attach_calendar: true,
+    },
+    Reminder {
+        key: "one-week",
+        template_type: ONE_WEEK_REMINDER_TEMPLATE,
+        name: "One Week Reminder",
+        subject: "One week to go! Sam & Jonah's wedding",
+        description: "One week before the wedding, to confirmed attendees",
+        audience: ReminderAudience::Attending,
+        attach_calendar: true,
+    },
+    Reminder {
+        key: "thank-you",
+        template_type: THANK_YOU_TEMPLATE,
+        name: "Thank You",
+        subject: "Thank you for celebrating with us!",
+        description: "The day after the wedding, to confirmed attendees",
+        audience: ReminderAudience::Attending,
+        attach_calendar: false,
+    },
+];
+
+pub fn find(key: &str) -> Option<&'static Reminder> {
+    REMINDERS.iter().find(|r| r.key == key)
+}
+
+pub fn for_template(template_type: &str) -> Option<&'static Reminder> {
+    REMINDERS.iter().find(|r| r.template_type == template_type)
+}
+
+impl Reminder {
+    /// Key for one delivery of this reminder. It is sent to Resend as the
+    /// Idempotency-Key and stored as `email_sends.reminder_key`, so a retried
+    /// send never reaches an invite twice.
+    pub fn idempotency_key(&self, campaign_id: Uuid, invite_id: Uuid) -> String {
+        format!("{}_{}_{}", self.template_type, campaign_id, invite_id)
+    }
+
+    /// The reminder's campaign, created the first time it is needed.
+    pub async fn campaign(&self, db: &PgPool) -> Result<EmailCampaign, sqlx::Error> {
+        sqlx::query_as::<_, EmailCampaign>(
+            "INSERT INTO email_campaigns (name, subject, template_type, reminder_type)
+             VALUES ($1, $2, $3, $3)
+             ON CONFLICT (reminder_type) WHERE reminder_type IS NOT NULL
+             DO UPDATE SET reminder_type = EXCLUDED.reminder_type
+             RETURNING *"
+        )
+        .bind(self.name)
+        .bind(self.subject)
+        .bind(self.template_type)
+        .fetch_one(db)
+        .await
+    }
+
+    /// Campaign subject if the campaign exists, without creating it.
+    pub async fn current_subject(&self, db: &PgPool) -> Result<String, sqlx::Error> {
+        let subject = sqlx::query_scalar::<_, String>(
+            "SELECT subject FROM email_campaigns WHERE reminder_type = $1"
+        )
+        .bind(self.template_type)
+        .fetch_optional(db)
+        .await?;
+        Ok(subject.unwrap_or_else(|| self.subject.to_string()))
+    }
+
+    /// Every invite in the audience, with only the guests the reminder is
+    /// addressed to.
+    pub async fn audience(&self, db: &PgPool) -> Result<Vec<InviteWithGuests>, sqlx::Error> {
+        segments::matching_invites(db, &self.audience.filters(), None).await
+    }
+
+    /// Guests on one invite the reminder is addressed to.
+    pub async fn invite_guests(&self, db: &PgPool, invite_id: Uuid) -> Result<Vec<Guest>, sqlx::Error> {
+        let guests = segments::matching_guests(db, &self.audience.filters(), None).await?;
+        Ok(guests.into_iter().filter(|g| g.invite_id == Some(invite_id)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, for_template, REMINDERS};
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    #[test]
+    fn reminder_idempotency_key_is_stable_per_campaign_and_invite() {
+        let campaign_id = Uuid::parse_str("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa").unwrap();
+        let invite_id = Uuid::parse_str("bbbbbbbb-bbbb-bbbb-bbbb-bbbbbbbbbbbb").unwrap();
+        let one_month = find("one-month").unwrap();
+
+        let first = one_month.idempotency_key(campaign_id, invite_id);
+        let retry = one_month.idempotency_key(campaign_id, invite_id);
+
+        assert_eq!(first, retry);
+        // Keys already stored for one-month reminders keep matching
+        assert_eq!(first, format!("one_month_reminder_{}_{}", campaign_id, invite_id));
+        assert!(first.len() <= 255);
+
+        let keys: HashSet<_> = REMINDERS.iter().map(|r| r.idempotency_key(campaign_id, invite_id)).collect();
+        assert_eq!(keys.len(), REMINDERS.len());
+        assert!(REMINDERS.iter().all(|r| for_template(r.template_type).is_some_and(|found| found.key == r.key)));
+    }
+}
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{ConnectInfo, Path, State},
+    extract::{ConnectInfo, Path, Query, State},
     http::{StatusCode, header},
     response::Html,
     routing::{get, post},
@@ -14,10 +14,21 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use std::time::Instant;
 
+use crate::calendar;
+use crate::dietary;
+use crate::segments;
+use crate::songs;
+use crate::notifications;
+use crate::reminders::{self, Reminder};
 use crate::email::{
-    EmailService, ONE_MONTH_REMINDER_NAME, ONE_MONTH_REMINDER_SUBJECT,
-    ONE_MONTH_REMINDER_TEMPLATE,
+    EmailService, is_valid_email, MAX_TEST_RECIPIENTS, RSVP_CONFIRMATION_NAME, RSVP_CONFIRMATION_SUBJECT,
+    RSVP_CONFIRMATION_TEMPLATE,
 };
+use crate::email::templates::{escape_html, RsvpSummary};
+use crate::email::inbound;
+use crate::email::provider;
+use crate::email::suppression;
+use crate::email::template_store::{self, INVITATION_TEMPLATE};
 use crate::models::{
     EmailCampaign, EmailSend, Guest, Invite, InviteWithGuests, Rsvp,
     HoneymoonCategory, HoneymoonItem, RegistryContribution,
@@ -25,9 +36,37 @@ use crate::models::{
     CreateItemRequest, UpdateItemRequest,
     CreateContributionRequest, UpdateContributionRequest,
     CategoryWithItems, ItemWithContributions, PublicContribution, RegistryStats,
-    RsvpStats, RsvpWithGuest, AdminRsvpEntry,
-    InviteRsvpSubmission, InviteRsvpResponse,
+    RsvpStats, RsvpWithGuest, AdminRsvpEntry, AgeGroupStats,
+    AGE_ADULT, AGE_GROUPS, MAX_ADULTS_PER_INVITE, age_group_for,
+    RsvpAnalytics, RsvpBreakdownRow, DailyResponses, OpenedNotResponded,
+    RsvpFieldChange, RsvpRevisionWithGuest, RSVP_SOURCE_GUEST_LINK,
+    AdminRsvpSubmission, ADMIN_RSVP_CHANNELS,
+    GuestRsvpEntry, InviteRsvpSubmission, InviteRsvpResponse,
+    FindInviteRequest, FindInviteResponse,
+    MenuCourse, MenuOption, CourseWithOptions, GuestMealSelection,
+    CreateMenuCourseRequest, CreateMenuOptionRequest,
+    MealOptionTotal, GuestMealRow, CatererReport,
+    DietaryGuest, DietaryGroup, DietaryReport, UpdateDietaryTagsRequest,
+    RsvpQuestion, RsvpAnswer, QuestionAnswer, CreateRsvpQuestionRequest,
+    QUESTION_SCOPE_GUEST, QUESTION_SCOPE_INVITE, QUESTION_TYPES,
+    Event, EventAttendance, EventStats, GuestEventResponse,
+    CreateEventRequest, SetEventInvitesRequest,
+    NotificationSettings, UpdateNotificationSettingsRequest, RsvpDigest,
+    Song, SongWithRequests, UpdateSongStatusRequest, PlaylistShareLink,
+    GuestbookEntry, PublicGuestbookEntry, CreateGuestbookPostRequest, GuestbookQuery,
+    UpdateGuestbookStatusRequest, GUESTBOOK_APPROVED, GUESTBOOK_STATUSES,
+    SONG_APPROVED, SONG_STATUSES,
+    NOTIFICATION_FREQUENCIES,
     SendInvitationRequest, SendInvitationResponse,
+    CampaignAudience, EmailQuota, EmailReply, EmailReplyWithGuest, GuestConversation, InboundEmailRequest, MarkReplyHandledRequest, ReplyQuery,
+    EmailSendLogEntry, DELIVERY_STATUSES, DELIVERY_SENT, DELIVERY_DELAYED, DELIVERY_DELIVERED,
+    DELIVERY_BOUNCED, DELIVERY_COMPLAINED,
+    EmailSuppression, CreateSuppressionRequest, SUPPRESS_BOUNCE, SUPPRESS_COMPLAINT,
+    SUPPRESS_MANUAL, SUPPRESS_OPT_OUT,
+    AudienceSegment, AudienceCount, SaveSegmentRequest, SegmentFilters, SetCampaignSegmentRequest,
+    CampaignPreview, CampaignPreviewQuery, TestSendRequest, TestSendResponse,
+    EmailTemplate, EmailTemplateVersion, EmailTemplateWithHistory, EmailTemplateVariable,
+    EmailTemplateVariables, SaveEmailTemplateRequest, RollbackEmailTemplateRequest,
 };
 use axum_extra::extract::Multipart;
 use rust_decimal::Decimal;
@@ -97,6 +136,15 @@ pub struct CreateGuestRequest {
     pub sam_or_jonah: String,
     pub maybe: bool,
     pub invite_type: String,
+    #[serde(default)]
+    pub postal_code: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// adult, child or infant; derived from `age` when omitted
+    #[serde(default)]
+    pub age_group: Option<String>,
+    #[serde(default)]
+    pub age: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -121,6 +169,8 @@ pub struct CreateCampaignRequest {
     pub name: String,
     pub subject: String,
     pub template_type: String,
+    #[serde(default)]
+    pub segment_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
@@ -138,6 +188,8 @@ pub struct RecipientStatus {
     pub sent_at: Option<time::OffsetDateTime>,
     pub opened_at: Option<time::OffsetDateTime>,
     pub opened_count: i32,
+    /// Delivery status from the provider's webhooks
+    pub status: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -145,18 +197,52 @@ pub struct SendCampaignResponse {
     pub success: bool,
     pub sent_count: usize,
     pub message: String,
+    /// Addresses skipped because they are on the suppression list
+    pub suppressed: Vec<String>,
+    /// Set when this month's email quota is nearly used up
+    pub quota_warning: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct OneMonthReminderRecipient {
+pub struct ReminderRecipient {
     pub invite: InviteWithGuests,
     pub sent_at: Option<time::OffsetDateTime>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct OneMonthReminderStatus {
+pub struct ReminderStatus {
+    pub reminder: &'static Reminder,
     pub subject: String,
-    pub recipients: Vec<OneMonthReminderRecipient>,
+    pub recipients: Vec<ReminderRecipient>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatererExportView {
+    #[default]
+    Guests,
+    Totals,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistFormat {
+    #[default]
+    Csv,
+    M3u,
+    Txt,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlaylistQuery {
+    #[serde(default)]
+    pub format: PlaylistFormat,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CatererExportQuery {
+    #[serde(default)]
+    pub view: CatererExportView,
 }
 
 pub fn admin_routes() -> Router<AppState> {
@@ -168,22 +254,74 @@ pub fn admin_routes() -> Router<AppState> {
         .route("/invites", get(list_invites).post(create_invite))
         .route("/invites/:id", get(get_invite).put(update_invite).delete(delete_invite))
         .route("/invites/auto-suggest", post(auto_suggest_invites))
+        .route("/rsvps/analytics", get(admin_rsvp_analytics))
+        .route("/guestbook", get(admin_list_guestbook))
+        .route("/guestbook/keepsake", get(admin_guestbook_keepsake))
+        .route("/guestbook/:id", axum::routing::put(admin_update_guestbook_status).delete(admin_delete_guestbook_entry))
+        .route("/songs", get(admin_list_songs))
+        .route("/songs/:id", axum::routing::put(admin_update_song_status))
+        .route("/songs/export", get(admin_export_songs))
+        .route("/songs/share-link", get(admin_get_playlist_link).post(admin_rotate_playlist_link))
+        .route("/invites/:id/rsvp", post(admin_submit_rsvp).put(admin_submit_rsvp))
+        .route("/invites/:id/rsvp-history", get(admin_rsvp_history))
+        .route("/invites/:id/emails", get(admin_invite_emails))
+        .route("/guests/:id/emails", get(admin_guest_emails))
+        .route("/guests/:id/conversation", get(admin_guest_conversation))
+        .route("/replies", get(admin_list_replies))
+        .route("/replies/:id/handled", axum::routing::put(admin_mark_reply_handled))
         .route("/campaigns", get(list_campaigns).post(create_campaign))
         .route("/campaigns/:id/preview", get(preview_campaign))
         .route("/campaigns/:id/send", post(send_campaign))
+        .route("/campaigns/:id/test-send", post(test_send_campaign))
+        .route("/campaigns/:id/segment", axum::routing::put(set_campaign_segment))
+        .route("/campaigns/:id/audience", get(campaign_audience_count))
+        .route("/email/quota", get(admin_email_quota))
+        .route("/segments", get(admin_list_segments).post(admin_create_segment))
+        .route("/segments/count", post(admin_count_segment_filters))
+        .route("/segments/:id", axum::routing::put(admin_update_segment).delete(admin_delete_segment))
+        .route("/segments/:id/count", get(admin_count_segment))
         .route("/campaigns/:id/stats", get(campaign_stats))
         .route("/campaigns/:id/recipients", get(campaign_recipients))
+        .route("/suppressions", get(admin_list_suppressions).post(admin_create_suppression))
+        .route("/suppressions/:email", axum::routing::delete(admin_delete_suppression))
+        .route("/email-templates", get(admin_list_email_templates))
+        .route("/email-templates/variables", get(admin_list_template_variables).put(admin_update_template_variables))
+        .route("/email-templates/:template_type", get(admin_get_email_template).put(admin_save_email_template))
+        .route("/email-templates/:template_type/rollback", post(admin_rollback_email_template))
         // RSVP admin routes
         .route("/rsvps", get(admin_list_rsvps))
         .route("/rsvps/stats", get(admin_rsvp_stats))
         .route("/rsvps/export", get(admin_export_rsvps))
+        .route("/rsvps/:id/dietary", axum::routing::put(admin_update_dietary_tags))
+        .route("/dietary/report", get(admin_dietary_report))
+        .route("/dietary/suggest", post(admin_suggest_dietary_tags))
+        .route("/notifications/settings", get(admin_get_notification_settings).put(admin_update_notification_settings))
+        .route("/notifications/digest", get(admin_preview_digest))
+        .route("/notifications/digest/send", post(admin_send_digest))
+        .route("/events", get(admin_list_events).post(admin_create_event))
+        .route("/events/:id", axum::routing::put(admin_update_event).delete(admin_delete_event))
+        .route("/events/:id/invites", get(admin_list_event_invites).put(admin_set_event_invites))
+        .route("/questions", get(admin_list_questions).post(admin_create_question))
+        .route("/questions/:id", axum::routing::put(admin_update_question).delete(admin_delete_question))
+        // Menu and caterer routes
+        .route("/menu", get(admin_list_menu))
+        .route("/menu/courses", post(admin_create_menu_course))
+        .route("/menu/courses/:id", axum::routing::put(admin_update_menu_course).delete(admin_delete_menu_course))
+        .route("/menu/options", post(admin_create_menu_option))
+        .route("/menu/options/:id", axum::routing::put(admin_update_menu_option).delete(admin_delete_menu_option))
+        .route("/meals/report", get(admin_caterer_report))
+        .route("/meals/report/export", get(admin_export_caterer_report))
+        .route("/meals/report/pdf", get(admin_caterer_report_pdf))
         // Invitation email routes
         .route("/invitations/send", post(admin_send_invitations))
+        .route("/invitations/test-send", post(admin_test_send_invitation))
         .route("/invitations/status", get(admin_invitation_status))
-        // One-month reminder routes
-        .route("/reminders/one-month/status", get(admin_one_month_reminder_status))
-        .route("/reminders/one-month/preview", get(admin_one_month_reminder_preview))
-        .route("/reminders/one-month/send", post(admin_send_one_month_reminders))
+        // Reminder routes, e.g. /reminders/one-month/send
+        .route("/reminders", get(admin_list_reminders))
+        .route("/reminders/:reminder/status", get(admin_reminder_status))
+        .route("/reminders/:reminder/preview", get(admin_reminder_preview))
+        .route("/reminders/:reminder/send", post(admin_send_reminders))
+        .route("/reminders/:reminder/test-send", post(admin_test_send_reminder))
         // Registry admin routes
         .route("/registry/categories", get(admin_list_categories).post(admin_create_category))
         .route("/registry/categories/:id", axum::routing::put(admin_update_category).delete(admin_delete_category))
@@ -198,8 +336,19 @@ pub fn admin_routes() -> Router<AppState> {
 pub fn public_routes() -> Router<AppState> {
     Router::new()
         // Public RSVP routes
+        .route("/rsvp/find", post(rsvp_find))
         .route("/rsvp/:code", get(rsvp_lookup))
         .route("/rsvp/:code/submit", post(rsvp_submit))
+        // Guestbook
+        .route("/guestbook", get(public_guestbook).post(public_create_guestbook_post))
+        // DJ playlist via share link
+        .route("/playlist/:token", get(public_playlist))
+        // Wedding events for calendar apps
+        .route("/calendar.ics", get(public_calendar))
+        // Email delivery webhooks
+        .route("/webhooks/resend", post(resend_webhook))
+        .route("/webhooks/inbound-email", post(inbound_email_webhook))
+        .route("/email/opt-out/:token", get(public_opt_out_page).post(public_opt_out))
         // Public registry routes
         .route("/registry/categories", get(public_list_categories))
         .route("/registry/items/:id", get(public_get_item))
@@ -240,10 +389,11 @@ async fn create_guest(
     Json(req): Json<CreateGuestRequest>,
 ) -> Result<Json<Guest>, StatusCode> {
     let unique_code = Uuid::new_v4().to_string().replace("-", "")[..8].to_string();
+    let age_group = resolve_age_group(&req)?;
 
     let guest = sqlx::query_as::<_, Guest>(
-        "INSERT INTO guests (name, email, relationship, sam_or_jonah, maybe, unique_code, invite_type)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "INSERT INTO guests (name, email, relationship, sam_or_jonah, maybe, unique_code, invite_type, postal_code, tags, age_group, age)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
          RETURNING *"
     )
     .bind(&req.name)
@@ -253,6 +403,10 @@ async fn create_guest(
     .bind(req.maybe)
     .bind(&unique_code)
     .bind(&req.invite_type)
+    .bind(&req.postal_code)
+    .bind(normalize_tags(&req.tags))
+    .bind(age_group)
+    .bind(req.age)
     .fetch_one(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -260,16 +414,67 @@ async fn create_guest(
     Ok(Json(guest))
 }
 
+/// The guest's age group: as given, or implied by their age, or adult.
+fn resolve_age_group(req: &CreateGuestRequest) -> Result<&str, StatusCode> {
+    if req.age.is_some_and(|age| !(0..=120).contains(&age)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    match (req.age_group.as_deref(), req.age) {
+        (Some(group), _) if AGE_GROUPS.contains(&group) => Ok(group),
+        (Some(_), _) => Err(StatusCode::BAD_REQUEST),
+        (None, Some(age)) => Ok(age_group_for(age)),
+        (None, None) => Ok(AGE_ADULT),
+    }
+}
+
+/// An invite holds at least one guest and at most two adults (the guest and
+/// a plus one); children and infants can be added on top.
+async fn validate_invite_guests<'e>(
+    db: impl sqlx::PgExecutor<'e>,
+    guest_ids: &[Uuid],
+) -> Result<(), StatusCode> {
+    if guest_ids.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let adults = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM guests WHERE id = ANY($1) AND age_group = $2"
+    )
+    .bind(guest_ids)
+    .bind(AGE_ADULT)
+    .fetch_one(db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if adults as usize > MAX_ADULTS_PER_INVITE {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(())
+}
+
+/// Trimmed, lowercased, de-duplicated guest tags.
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags.iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
 // Update guest
 async fn update_guest(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
     Json(req): Json<CreateGuestRequest>,
 ) -> Result<Json<Guest>, StatusCode> {
+    let age_group = resolve_age_group(&req)?;
+
+    let mut tx = state.db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let guest = sqlx::query_as::<_, Guest>(
         "UPDATE guests
-         SET name = $1, email = $2, relationship = $3, sam_or_jonah = $4, maybe = $5, invite_type = $6, updated_at = NOW()
-         WHERE id = $7
+         SET name = $1, email = $2, relationship = $3, sam_or_jonah = $4, maybe = $5, invite_type = $6, postal_code = $7, tags = $8, age_group = $9, age = $10, updated_at = NOW()
+         WHERE id = $11
          RETURNING *"
     )
     .bind(&req.name)
@@ -278,11 +483,27 @@ async fn update_guest(
     .bind(&req.sam_or_jonah)
     .bind(req.maybe)
     .bind(&req.invite_type)
+    .bind(&req.postal_code)
+    .bind(normalize_tags(&req.tags))
+    .bind(age_group)
+    .bind(req.age)
     .bind(id)
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|_| StatusCode::NOT_FOUND)?;
 
+    // A new age can make a child an adult, so the invite's limits are
+    // checked again with the change in place
+    if let Some(invite_id) = guest.invite_id {
+        let guest_ids = sqlx::query_scalar::<_, Uuid>("SELECT id FROM guests WHERE invite_id = $1")
+            .bind(invite_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        validate_invite_guests(&mut *tx, &guest_ids).await?;
+    }
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(Json(guest))
 }
 
@@ -440,10 +661,8 @@ async fn create_invite(
     State(state): State<AppState>,
     Json(req): Json<CreateInviteRequest>,
 ) -> Result<Json<InviteWithGuests>, StatusCode> {
-    // Validate: 1-2 guests only
-    if req.guest_ids.is_empty() || req.guest_ids.len() > 2 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    // Validate: 1-2 adults, plus any children
+    validate_invite_guests(&state.db, &req.guest_ids).await?;
 
     // Generate unique code
     let unique_code = format!("{:08x}", rand::random::<u32>());
@@ -489,10 +708,8 @@ async fn update_invite(
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateInviteRequest>,
 ) -> Result<Json<InviteWithGuests>, StatusCode> {
-    // Validate: 1-2 guests only
-    if req.guest_ids.is_empty() || req.guest_ids.len() > 2 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    // Validate: 1-2 adults, plus any children
+    validate_invite_guests(&state.db, &req.guest_ids).await?;
 
     // Update invite type
     let invite = sqlx::query_as::<_, Invite>(
@@ -642,14 +859,20 @@ async fn create_campaign(
     State(state): State<AppState>,
     Json(req): Json<CreateCampaignRequest>,
 ) -> Result<Json<EmailCampaign>, StatusCode> {
+    // Invitations have a single campaign, sent from the invitations page
+    if req.template_type == INVITATION_TEMPLATE {
+        return Err(StatusCode::CONFLICT);
+    }
+
     let campaign = sqlx::query_as::<_, EmailCampaign>(
-        "INSERT INTO email_campaigns (name, subject, template_type)
-         VALUES ($1, $2, $3)
+        "INSERT INTO email_campaigns (name, subject, template_type, segment_id)
+         VALUES ($1, $2, $3, $4)
          RETURNING *"
     )
     .bind(&req.name)
     .bind(&req.subject)
     .bind(&req.template_type)
+    .bind(req.segment_id)
     .fetch_one(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -657,60 +880,174 @@ async fn create_campaign(
     Ok(Json(campaign))
 }
 
-// Preview campaign email
-async fn preview_campaign(
-    State(state): State<AppState>,
-    Path(_id): Path<Uuid>,
-) -> Result<Html<String>, StatusCode> {
-    // Get a sample invite for preview
-    let invite = sqlx::query_as::<_, Invite>(
-        "SELECT * FROM invites LIMIT 1"
+/// The guests a template is addressed to on an invite. Reminders go to
+/// their audience; everything else goes to every guest still on the list.
+async fn template_recipients(db: &PgPool, template_type: &str, invite_id: Uuid) -> Result<Vec<Guest>, sqlx::Error> {
+    if let Some(reminder) = reminders::for_template(template_type) {
+        return reminder.invite_guests(db, invite_id).await;
+    }
+    sqlx::query_as::<_, Guest>(
+        "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
     )
-    .fetch_optional(&state.db)
+    .bind(invite_id)
+    .fetch_all(db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let invite = match invite {
-        Some(inv) => inv,
-        None => {
-            // No invites, create sample data
-            return Ok(Html(format!(
-                "<html><body><h1>No invites found</h1><p>Create some invites first to preview the email template.</p></body></html>"
-            )));
-        }
-    };
+}
 
-    // Get guests for this invite
-    let guests = sqlx::query_as::<_, Guest>(
-        "SELECT * FROM guests WHERE invite_id = $1"
-    )
-    .bind(invite.id)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+/// The invite to render a template for, by id or code, or the first invite
+/// with guests when none is given. Guests are the template's recipients.
+async fn template_invite(
+    db: &PgPool,
+    template_type: &str,
+    invite: Option<&str>,
+) -> Result<InviteWithGuests, StatusCode> {
+    let invite = match invite.map(str::trim).filter(|i| !i.is_empty()) {
+        Some(invite) => sqlx::query_as::<_, Invite>(
+            "SELECT * FROM invites WHERE id::text = $1 OR unique_code = $1"
+        )
+        .bind(invite)
+        .fetch_optional(db)
+        .await,
+        None => sqlx::query_as::<_, Invite>(
+            "SELECT i.* FROM invites i
+             WHERE EXISTS (SELECT 1 FROM guests g WHERE g.invite_id = i.id AND g.removed = false)
+             ORDER BY i.created_at
+             LIMIT 1"
+        )
+        .fetch_optional(db)
+        .await,
+    }
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
 
-    let invite_with_guests = InviteWithGuests {
-        invite,
-        guests,
-    };
+    let guests = template_recipients(db, template_type, invite.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(InviteWithGuests { invite, guests })
+}
 
-    // Create email service (API key not needed for preview)
+/// Email service configured from the environment. Previews pass an empty
+/// API key since they never reach the provider.
+pub(crate) fn configured_email_service(db: &PgPool, resend_api_key: String) -> EmailService {
     let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let resend_api_key = std::env::var("RESEND_API_KEY")
-        .unwrap_or_else(|_| "".to_string());
+        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
     let from_email = std::env::var("FROM_EMAIL")
         .unwrap_or_else(|_| "contact@samandjonah.com".to_string());
     let venue_map_url = std::env::var("VENUE_MAP_URL")
         .unwrap_or_else(|_| "https://maps.google.com".to_string());
     let hotel_info_url = std::env::var("HOTEL_INFO_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let email_service = EmailService::new(state.db.clone(), frontend_url, resend_api_key, from_email, venue_map_url, hotel_info_url);
+        .unwrap_or_else(|_| format!("{}/travel", frontend_url));
+
+    EmailService::new(db.clone(), frontend_url, resend_api_key, from_email, venue_map_url, hotel_info_url)
+}
+
+/// Email service configured from the environment for sending.
+fn sending_email_service(db: &PgPool) -> Result<EmailService, StatusCode> {
+    let resend_api_key = std::env::var("RESEND_API_KEY")
+        .map_err(|_| {
+            tracing::error!("RESEND_API_KEY environment variable not set");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(configured_email_service(db, resend_api_key))
+}
+
+/// Render a template for an invite and send it to admin-supplied addresses
+/// only. Test sends never write email_sends or mark invites as sent.
+async fn send_test_email(
+    db: &PgPool,
+    template_type: &str,
+    subject: Option<&str>,
+    req: TestSendRequest,
+) -> Result<Json<TestSendResponse>, StatusCode> {
+    let to: Vec<String> = req.to.iter().map(|addr| addr.trim().to_string()).collect();
+    if to.is_empty() || to.len() > MAX_TEST_RECIPIENTS || !to.iter().all(|addr| is_valid_email(addr)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let invite = template_invite(db, template_type, req.invite.as_deref()).await?;
+    let email_service = sending_email_service(db)?;
+    let subject = email_service
+        .send_test(template_type, subject, &invite, &to)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to send test email: {}", e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    Ok(Json(TestSendResponse {
+        template_type: template_type.to_string(),
+        invite_code: invite.invite.unique_code,
+        subject,
+        to,
+    }))
+}
+
+async fn test_send_campaign(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<TestSendRequest>,
+) -> Result<Json<TestSendResponse>, StatusCode> {
+    let campaign = sqlx::query_as::<_, EmailCampaign>("SELECT * FROM email_campaigns WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    send_test_email(&state.db, &campaign.template_type, Some(&campaign.subject), req).await
+}
+
+// Preview a campaign email for one invite (`?invite=` id or code), with the
+// rendered subject, plain-text version and any placeholders left empty
+async fn preview_campaign(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<CampaignPreviewQuery>,
+) -> Result<Json<CampaignPreview>, StatusCode> {
+    let campaign = sqlx::query_as::<_, EmailCampaign>("SELECT * FROM email_campaigns WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let template = template_store::load_template(&state.db, &campaign.template_type)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or_else(|| {
+            tracing::warn!("Campaign {} uses unknown template '{}'", id, campaign.template_type);
+            StatusCode::UNPROCESSABLE_ENTITY
+        })?;
+
+    let invite = template_invite(&state.db, &campaign.template_type, query.invite.as_deref()).await?;
 
-    // Generate preview HTML
-    let html = email_service.render_save_the_date(&invite_with_guests);
+    // Create email service (API key not needed for preview)
+    let email_service = configured_email_service(&state.db, String::new());
+
+    let ctx = email_service.template_context(&invite).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let subject = Some(campaign.subject.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(&template.subject);
+    let email = template_store::render_source(subject, &template.html, &ctx).map_err(|e| {
+        tracing::error!("Failed to render campaign preview: {:#}", e);
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
 
-    Ok(Html(html))
+    Ok(Json(CampaignPreview {
+        campaign_id: campaign.id,
+        template_type: campaign.template_type,
+        invite_id: invite.invite.id,
+        invite_code: invite.invite.unique_code.clone(),
+        recipients: invite.guests.iter().map(|g| g.email.clone()).collect(),
+        empty_placeholders: template_store::empty_placeholders(subject, &template.html, &ctx),
+        subject: email.subject,
+        html: email.html,
+        text: email.text,
+    }))
 }
 
 // Send campaign
@@ -718,27 +1055,38 @@ async fn send_campaign(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<SendCampaignResponse>, StatusCode> {
-    let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let resend_api_key = std::env::var("RESEND_API_KEY")
-        .map_err(|_| {
-            tracing::error!("RESEND_API_KEY environment variable not set");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let from_email = std::env::var("FROM_EMAIL")
-        .unwrap_or_else(|_| "contact@samandjonah.com".to_string());
-    let venue_map_url = std::env::var("VENUE_MAP_URL")
-        .unwrap_or_else(|_| "https://maps.google.com".to_string());
-    let hotel_info_url = std::env::var("HOTEL_INFO_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
+    let email_service = sending_email_service(&state.db)?;
 
-    let email_service = EmailService::new(state.db.clone(), frontend_url, resend_api_key, from_email, venue_map_url, hotel_info_url);
+    let campaign = sqlx::query_as::<_, EmailCampaign>("SELECT * FROM email_campaigns WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let audience = segments::campaign_audience(&state.db, &campaign)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let quota = email_quota(&state.db).await?;
+    let planned = segments::count(&audience).recipients;
+    if let Some(refusal) = provider::refusal(&quota, planned) {
+        tracing::warn!("Refused to send campaign {}: {}", id, refusal);
+        return Ok(Json(SendCampaignResponse {
+            success: false,
+            sent_count: 0,
+            message: refusal,
+            suppressed: Vec::new(),
+            quota_warning: None,
+        }));
+    }
+    let quota_warning = provider::warning(&quota, planned);
 
     match email_service.send_campaign(id).await {
-        Ok(sent_count) => Ok(Json(SendCampaignResponse {
+        Ok(summary) => Ok(Json(SendCampaignResponse {
             success: true,
-            sent_count,
-            message: format!("Successfully sent {} emails via Resend", sent_count),
+            sent_count: summary.sent_count,
+            message: format!("Successfully sent {} emails via Resend", summary.sent_count),
+            suppressed: summary.suppressed,
+            quota_warning,
         })),
         Err(e) => {
             tracing::error!("Failed to send campaign: {}", e);
@@ -753,32 +1101,32 @@ async fn campaign_stats(
     Path(id): Path<Uuid>,
 ) -> Result<Json<CampaignStats>, StatusCode> {
     // Count total invites with non-removed guests
-    let total_invites: i64 = sqlx::query_scalar!(
+    let total_invites: i64 = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(DISTINCT invite_id) FROM guests WHERE removed = false AND invite_id IS NOT NULL"
     )
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Count sent emails for this campaign
-    let sent_count: i64 = sqlx::query_scalar!(
-        "SELECT COUNT(*) FROM email_sends WHERE campaign_id = $1",
-        id
+    // Invites sent this campaign and how many opened it; an invite sent
+    // more than once counts once
+    let (sent_count, opened_count) = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT COUNT(DISTINCT invite_id),
+                COUNT(DISTINCT invite_id) FILTER (WHERE opened_at IS NOT NULL)
+         FROM email_sends
+         WHERE campaign_id = $1"
     )
+    .bind(id)
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
-
-    let pending_count = total_invites - sent_count;
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(Json(CampaignStats {
         total_invites,
         sent_count,
-        opened_count: 0, // Tracking now done via Resend dashboard
-        not_opened_count: 0, // Tracking now done via Resend dashboard
-        pending_count,
+        opened_count,
+        not_opened_count: sent_count - opened_count,
+        pending_count: (total_invites - sent_count).max(0),
     }))
 }
 
@@ -825,8 +1173,9 @@ async fn campaign_recipients(
                         guests,
                     },
                     sent_at: send.sent_at,
-                    opened_at: None, // Tracking now done via Resend dashboard
-                    opened_count: 0, // Tracking now done via Resend dashboard
+                    opened_at: send.opened_at,
+                    opened_count: send.opened_count,
+                    status: send.status,
                 });
             }
         }
@@ -835,836 +1184,4172 @@ async fn campaign_recipients(
     Ok(Json(recipients))
 }
 
-// ============ TRACKING ROUTES ============
-
-// ============ REGISTRY PUBLIC ROUTES ============
+// ============ AUDIENCE SEGMENT ROUTES ============
 
-// List all categories with their items (public)
-async fn public_list_categories(State(state): State<AppState>) -> Result<Json<Vec<CategoryWithItems>>, StatusCode> {
-    let categories = sqlx::query_as::<_, HoneymoonCategory>(
-        "SELECT * FROM honeymoon_categories ORDER BY display_order, name"
+async fn admin_list_segments(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AudienceSegment>>, StatusCode> {
+    let segments = sqlx::query_as::<_, AudienceSegment>(
+        "SELECT * FROM audience_segments ORDER BY name"
     )
     .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let mut result = Vec::new();
-    for category in categories {
-        let items = sqlx::query_as::<_, HoneymoonItem>(
-            "SELECT * FROM honeymoon_items WHERE category_id = $1 ORDER BY display_order, name"
-        )
-        .bind(category.id)
-        .fetch_all(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(segments))
+}
 
-        result.push(CategoryWithItems {
-            category,
-            items,
-        });
+async fn admin_create_segment(
+    State(state): State<AppState>,
+    Json(req): Json<SaveSegmentRequest>,
+) -> Result<Json<AudienceSegment>, StatusCode> {
+    let filters = segments::validate_filters(&req.filters).ok_or(StatusCode::BAD_REQUEST)?;
+    if req.name.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
     }
 
-    // Also add items without a category
-    let uncategorized_items = sqlx::query_as::<_, HoneymoonItem>(
-        "SELECT * FROM honeymoon_items WHERE category_id IS NULL ORDER BY display_order, name"
+    let segment = sqlx::query_as::<_, AudienceSegment>(
+        "INSERT INTO audience_segments (name, filters) VALUES ($1, $2) RETURNING *"
     )
-    .fetch_all(&state.db)
+    .bind(req.name.trim())
+    .bind(sqlx::types::Json(filters))
+    .fetch_one(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    if !uncategorized_items.is_empty() {
-        result.push(CategoryWithItems {
-            category: HoneymoonCategory {
-                id: Uuid::nil(),
-                name: "Other".to_string(),
-                display_order: 9999,
-                created_at: None,
-            },
-            items: uncategorized_items,
-        });
-    }
-
-    Ok(Json(result))
+    Ok(Json(segment))
 }
 
-// Get single item with public contributions
-async fn public_get_item(
+async fn admin_update_segment(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ItemWithContributions>, StatusCode> {
-    let item = sqlx::query_as::<_, HoneymoonItem>(
-        "SELECT * FROM honeymoon_items WHERE id = $1"
-    )
-    .bind(id)
-    .fetch_one(&state.db)
-    .await
-    .map_err(|_| StatusCode::NOT_FOUND)?;
+    Json(req): Json<SaveSegmentRequest>,
+) -> Result<Json<AudienceSegment>, StatusCode> {
+    let filters = segments::validate_filters(&req.filters).ok_or(StatusCode::BAD_REQUEST)?;
+    if req.name.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
 
-    // Get all contributions for display (pending shown as if confirmed)
-    let contributions = sqlx::query_as::<_, RegistryContribution>(
-        "SELECT * FROM registry_contributions WHERE item_id = $1 AND status != 'rejected' ORDER BY created_at DESC"
+    let segment = sqlx::query_as::<_, AudienceSegment>(
+        "UPDATE audience_segments SET name = $1, filters = $2, updated_at = NOW()
+         WHERE id = $3
+         RETURNING *"
     )
+    .bind(req.name.trim())
+    .bind(sqlx::types::Json(filters))
     .bind(id)
-    .fetch_all(&state.db)
+    .fetch_optional(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let public_contributions: Vec<PublicContribution> = contributions
-        .into_iter()
-        .map(|c| PublicContribution {
-            // Hide all contributor names on public endpoint
-            display_name: "A Generous Guest".to_string(),
-            amount: c.amount,
-            message: c.message,
-            created_at: c.created_at,
-        })
-        .collect();
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(ItemWithContributions {
-        item,
-        contributions: public_contributions,
-    }))
+    Ok(Json(segment))
 }
 
-// Create contribution (public)
-async fn public_create_contribution(
+async fn admin_delete_segment(
     State(state): State<AppState>,
-    Json(req): Json<CreateContributionRequest>,
-) -> Result<Json<RegistryContribution>, StatusCode> {
-    // If item_id is provided, verify the item exists and isn't fully funded
-    if let Some(item_id) = req.item_id {
-        let item = sqlx::query_as::<_, HoneymoonItem>(
-            "SELECT * FROM honeymoon_items WHERE id = $1"
-        )
-        .bind(item_id)
-        .fetch_optional(&state.db)
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    let result = sqlx::query("DELETE FROM audience_segments WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        if item.is_none() {
-            return Err(StatusCode::NOT_FOUND);
-        }
-
-        if item.unwrap().is_fully_funded {
-            return Err(StatusCode::BAD_REQUEST);
-        }
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
     }
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    let contribution = sqlx::query_as::<_, RegistryContribution>(
-        "INSERT INTO registry_contributions (item_id, contributor_name, contributor_email, amount, is_anonymous, message, purpose)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)
-         RETURNING *"
-    )
-    .bind(req.item_id)
-    .bind(&req.contributor_name)
-    .bind(&req.contributor_email)
-    .bind(req.amount)
-    .bind(req.is_anonymous)
-    .bind(&req.message)
-    .bind(&req.purpose)
-    .fetch_one(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Immediately update item's total (treat pending as confirmed for display)
-    if let Some(item_id) = req.item_id {
-        let item = sqlx::query_as::<_, HoneymoonItem>(
-            "SELECT * FROM honeymoon_items WHERE id = $1"
-        )
-        .bind(item_id)
-        .fetch_one(&state.db)
+// Live count for filters being edited, before they are saved
+async fn admin_count_segment_filters(
+    State(state): State<AppState>,
+    Json(filters): Json<SegmentFilters>,
+) -> Result<Json<AudienceCount>, StatusCode> {
+    let filters = segments::validate_filters(&filters).ok_or(StatusCode::BAD_REQUEST)?;
+    let invites = segments::matching_invites(&state.db, &filters, None)
         .await
-        .ok();
-
-        if let Some(item) = item {
-            // Sum all non-rejected contributions
-            let total: Decimal = sqlx::query_scalar::<_, Decimal>(
-                "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
-            )
-            .bind(item_id)
-            .fetch_one(&state.db)
-            .await
-            .unwrap_or(Decimal::ZERO);
-
-            let is_fully_funded = total >= item.price;
-            let _ = sqlx::query(
-                "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
-            )
-            .bind(total)
-            .bind(is_fully_funded)
-            .bind(item_id)
-            .execute(&state.db)
-            .await;
-        }
-    }
-
-    Ok(Json(contribution))
-}
-
-// ============ REGISTRY ADMIN ROUTES ============
-
-// List all categories (admin)
-async fn admin_list_categories(State(state): State<AppState>) -> Result<Json<Vec<HoneymoonCategory>>, StatusCode> {
-    let categories = sqlx::query_as::<_, HoneymoonCategory>(
-        "SELECT * FROM honeymoon_categories ORDER BY display_order, name"
-    )
-    .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(categories))
+    Ok(Json(segments::count(&invites)))
 }
 
-// Create category (admin)
-async fn admin_create_category(
+async fn admin_count_segment(
     State(state): State<AppState>,
-    Json(req): Json<CreateCategoryRequest>,
-) -> Result<Json<HoneymoonCategory>, StatusCode> {
-    let display_order = req.display_order.unwrap_or(0);
-
-    let category = sqlx::query_as::<_, HoneymoonCategory>(
-        "INSERT INTO honeymoon_categories (name, display_order) VALUES ($1, $2) RETURNING *"
+    Path(id): Path<Uuid>,
+) -> Result<Json<AudienceCount>, StatusCode> {
+    let filters = sqlx::query_scalar::<_, sqlx::types::Json<SegmentFilters>>(
+        "SELECT filters FROM audience_segments WHERE id = $1"
     )
-    .bind(&req.name)
-    .bind(display_order)
-    .fetch_one(&state.db)
+    .bind(id)
+    .fetch_optional(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(category))
+    let invites = segments::matching_invites(&state.db, &filters, None)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(segments::count(&invites)))
 }
 
-// Update category (admin)
-async fn admin_update_category(
+// Attach a saved segment to a campaign, or clear it to send to everyone
+async fn set_campaign_segment(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(req): Json<UpdateCategoryRequest>,
-) -> Result<Json<HoneymoonCategory>, StatusCode> {
-    let display_order = req.display_order.unwrap_or(0);
-
-    let category = sqlx::query_as::<_, HoneymoonCategory>(
-        "UPDATE honeymoon_categories SET name = $1, display_order = $2 WHERE id = $3 RETURNING *"
+    Json(req): Json<SetCampaignSegmentRequest>,
+) -> Result<Json<EmailCampaign>, StatusCode> {
+    let campaign = sqlx::query_as::<_, EmailCampaign>(
+        "UPDATE email_campaigns SET segment_id = $1 WHERE id = $2 RETURNING *"
     )
-    .bind(&req.name)
-    .bind(display_order)
+    .bind(req.segment_id)
     .bind(id)
-    .fetch_one(&state.db)
+    .fetch_optional(&state.db)
     .await
-    .map_err(|_| StatusCode::NOT_FOUND)?;
+    .map_err(|e| {
+        // Unknown segment ids fail the foreign key
+        tracing::warn!("Failed to set campaign segment: {}", e);
+        StatusCode::BAD_REQUEST
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(category))
+    Ok(Json(campaign))
 }
 
-// Delete category (admin)
-async fn admin_delete_category(
+// Who a send would reach right now: the campaign's audience minus invites
+// that already received it
+async fn campaign_audience_count(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
-    sqlx::query("DELETE FROM honeymoon_categories WHERE id = $1")
+) -> Result<Json<CampaignAudience>, StatusCode> {
+    let campaign = sqlx::query_as::<_, EmailCampaign>("SELECT * FROM email_campaigns WHERE id = $1")
         .bind(id)
-        .execute(&state.db)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let invites = segments::campaign_audience(&state.db, &campaign)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let count = segments::count(&invites);
+    let quota = email_quota(&state.db).await?;
+    let refusal = provider::refusal(&quota, count.recipients);
+
+    Ok(Json(CampaignAudience {
+        exceeds_quota: refusal.is_some(),
+        quota_warning: refusal.or_else(|| provider::warning(&quota, count.recipients)),
+        count,
+        quota,
+    }))
+}
 
-    Ok(StatusCode::NO_CONTENT)
+// ============ EMAIL QUOTA ROUTES ============
+
+async fn email_quota(db: &PgPool) -> Result<EmailQuota, StatusCode> {
+    provider::usage(db).await.map_err(|e| {
+        tracing::error!("Failed to load email quota usage: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
 }
 
-// List all items (admin)
-async fn admin_list_items(State(state): State<AppState>) -> Result<Json<Vec<HoneymoonItem>>, StatusCode> {
-    let items = sqlx::query_as::<_, HoneymoonItem>(
-        "SELECT * FROM honeymoon_items ORDER BY display_order, name"
+async fn admin_email_quota(State(state): State<AppState>) -> Result<Json<EmailQuota>, StatusCode> {
+    Ok(Json(email_quota(&state.db).await?))
+}
+
+// ============ EMAIL SUPPRESSION ROUTES ============
+
+async fn admin_list_suppressions(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<EmailSuppression>>, StatusCode> {
+    let suppressions = sqlx::query_as::<_, EmailSuppression>(
+        "SELECT * FROM email_suppressions ORDER BY created_at DESC"
     )
     .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(items))
+    Ok(Json(suppressions))
 }
 
-// Create item (admin)
-async fn admin_create_item(
+// Block an address by hand
+async fn admin_create_suppression(
     State(state): State<AppState>,
-    Json(req): Json<CreateItemRequest>,
-) -> Result<Json<HoneymoonItem>, StatusCode> {
-    let display_order = req.display_order.unwrap_or(0);
-
-    let item = sqlx::query_as::<_, HoneymoonItem>(
-        "INSERT INTO honeymoon_items (category_id, name, description, price, display_order)
-         VALUES ($1, $2, $3, $4, $5)
-         RETURNING *"
+    Json(req): Json<CreateSuppressionRequest>,
+) -> Result<Json<EmailSuppression>, StatusCode> {
+    let email = req.email.trim().to_lowercase();
+    if !is_valid_email(&email) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let guest_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM guests WHERE LOWER(email) = $1 LIMIT 1"
     )
-    .bind(req.category_id)
-    .bind(&req.name)
-    .bind(&req.description)
-    .bind(req.price)
-    .bind(display_order)
-    .fetch_one(&state.db)
+    .bind(&email)
+    .fetch_optional(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(item))
-}
-
-// Update item (admin)
-async fn admin_update_item(
-    State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateItemRequest>,
-) -> Result<Json<HoneymoonItem>, StatusCode> {
-    let display_order = req.display_order.unwrap_or(0);
+    suppression::suppress(&state.db, &email, SUPPRESS_MANUAL, req.detail.as_deref(), guest_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let item = sqlx::query_as::<_, HoneymoonItem>(
-        "UPDATE honeymoon_items
-         SET category_id = $1, name = $2, description = $3, price = $4, display_order = $5, updated_at = NOW()
-         WHERE id = $6
-         RETURNING *"
+    let suppression = sqlx::query_as::<_, EmailSuppression>(
+        "SELECT * FROM email_suppressions WHERE email = $1"
     )
-    .bind(req.category_id)
-    .bind(&req.name)
-    .bind(&req.description)
-    .bind(req.price)
-    .bind(display_order)
-    .bind(id)
+    .bind(&email)
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::NOT_FOUND)?;
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(item))
+    Ok(Json(suppression))
 }
 
-// Delete item (admin)
-async fn admin_delete_item(
+// Allow an address again
+async fn admin_delete_suppression(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    Path(email): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
-    sqlx::query("DELETE FROM honeymoon_items WHERE id = $1")
-        .bind(id)
+    let result = sqlx::query("DELETE FROM email_suppressions WHERE email = $1")
+        .bind(email.trim().to_lowercase())
         .execute(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
     Ok(StatusCode::NO_CONTENT)
 }
 
-// Upload image for item (admin)
-async fn admin_upload_item_image(
-    State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    mut multipart: Multipart,
-) -> Result<Json<HoneymoonItem>, StatusCode> {
-    // Verify item exists
-    let _item = sqlx::query_as::<_, HoneymoonItem>(
-        "SELECT * FROM honeymoon_items WHERE id = $1"
-    )
-    .bind(id)
-    .fetch_one(&state.db)
-    .await
-    .map_err(|_| StatusCode::NOT_FOUND)?;
-
-    // Process the upload
-    while let Some(field) = multipart.next_field().await.map_err(|_| StatusCode::BAD_REQUEST)? {
-        let filename = field.file_name()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "image.jpg".to_string());
+fn opt_out_page(body: &str) -> Html<String> {
+    Html(format!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"UTF-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\
+         <title>Email preferences</title></head>\
+         <body style=\"font-family: Georgia, serif; max-width: 480px; margin: 60px auto; padding: 0 20px; color: #4a4a4a; text-align: center;\">\
+         {}</body></html>",
+        body
+    ))
+}
 
-        // Get file extension
-        let extension = filename.rsplit('.').next().unwrap_or("jpg");
-        let allowed_extensions = ["jpg", "jpeg", "png", "webp", "gif"];
-        if !allowed_extensions.contains(&extension.to_lowercase().as_str()) {
-            return Err(StatusCode::BAD_REQUEST);
-        }
+async fn opt_out_guest(db: &PgPool, token: Uuid) -> Result<Guest, StatusCode> {
+    sqlx::query_as::<_, Guest>("SELECT * FROM guests WHERE opt_out_token = $1")
+        .bind(token)
+        .fetch_optional(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
 
-        // Generate unique filename
-        let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
-        let file_path = format!("./uploads/registry/{}", new_filename);
+// Opt-out link from an email. Shows a confirm button rather than opting out
+// straight away, because link scanners open every link in an email.
+async fn public_opt_out_page(
+    State(state): State<AppState>,
+    Path(token): Path<Uuid>,
+) -> Result<Html<String>, StatusCode> {
+    let guest = opt_out_guest(&state.db, token).await?;
+
+    Ok(opt_out_page(&format!(
+        "<h1 style=\"color: #7d2248; font-weight: 400;\">Unsubscribe</h1>\
+         <p>Stop wedding updates and reminders to {}? You will still get your invitation and RSVP confirmations.</p>\
+         <form method=\"post\"><button type=\"submit\" style=\"background: #7d2248; color: #fff; border: 0; padding: 14px 32px; font-size: 15px; cursor: pointer;\">Unsubscribe</button></form>",
+        escape_html(&guest.email)
+    )))
+}
 
-        // Save file
-        let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
-        tokio::fs::write(&file_path, &data).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+async fn public_opt_out(
+    State(state): State<AppState>,
+    Path(token): Path<Uuid>,
+) -> Result<Html<String>, StatusCode> {
+    let guest = opt_out_guest(&state.db, token).await?;
+    if is_valid_email(&guest.email) {
+        suppression::suppress(&state.db, &guest.email, SUPPRESS_OPT_OUT, None, Some(guest.id))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        tracing::info!("{} opted out of non-essential email", guest.email);
+    }
 
-        // Update item with image URL
-        let image_url = format!("/uploads/registry/{}", new_filename);
-        let item = sqlx::query_as::<_, HoneymoonItem>(
-            "UPDATE honeymoon_items SET image_url = $1, updated_at = NOW() WHERE id = $2 RETURNING *"
-        )
-        .bind(&image_url)
-        .bind(id)
-        .fetch_one(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(opt_out_page(
+        "<h1 style=\"color: #7d2248; font-weight: 400;\">You're unsubscribed</h1>\
+         <p>We won't send you any more wedding updates or reminders.</p>"
+    ))
+}
 
-        return Ok(Json(item));
-    }
+// ============ EMAIL TEMPLATE ROUTES ============
 
-    Err(StatusCode::BAD_REQUEST)
+/// Template types and variable names are used as identifiers inside
+/// templates and tags, so keep them to lowercase letters, digits and `_`.
+fn valid_template_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 50
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
 }
 
-// List all contributions (admin)
-async fn admin_list_contributions(State(state): State<AppState>) -> Result<Json<Vec<RegistryContribution>>, StatusCode> {
-    let contributions = sqlx::query_as::<_, RegistryContribution>(
-        "SELECT * FROM registry_contributions ORDER BY created_at DESC"
+async fn admin_list_email_templates(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<EmailTemplate>>, StatusCode> {
+    let templates = sqlx::query_as::<_, EmailTemplate>(
+        "SELECT * FROM email_templates ORDER BY name"
     )
     .fetch_all(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .map_err(|e| {
+        tracing::error!("Failed to fetch email templates: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    Ok(Json(contributions))
+    Ok(Json(templates))
 }
 
-// Update contribution status (admin)
-async fn admin_update_contribution(
+async fn admin_get_email_template(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateContributionRequest>,
-) -> Result<Json<RegistryContribution>, StatusCode> {
-    // Get the contribution first to check item_id
-    let contribution = sqlx::query_as::<_, RegistryContribution>(
-        "SELECT * FROM registry_contributions WHERE id = $1"
+    Path(template_type): Path<String>,
+) -> Result<Json<EmailTemplateWithHistory>, StatusCode> {
+    let template = template_store::load_template(&state.db, &template_type)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let versions = sqlx::query_as::<_, EmailTemplateVersion>(
+        "SELECT * FROM email_template_versions WHERE template_type = $1 ORDER BY version DESC"
     )
-    .bind(id)
-    .fetch_one(&state.db)
+    .bind(&template_type)
+    .fetch_all(&state.db)
     .await
-    .map_err(|_| StatusCode::NOT_FOUND)?;
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let confirmed_at = if req.status == "confirmed" {
+    Ok(Json(EmailTemplateWithHistory { template, versions }))
+}
+
+/// Store new content as the next version of a template, creating the
+/// template when `name` is given and it does not exist yet. Saving content
+/// identical to the current version is a no-op.
+async fn save_template_version(
+    db: &PgPool,
+    template_type: &str,
+    name: Option<&str>,
+    subject: &str,
+    html: &str,
+) -> Result<Option<EmailTemplate>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let current = sqlx::query_as::<_, EmailTemplate>(
+        "SELECT * FROM email_templates WHERE template_type = $1 FOR UPDATE"
+    )
+    .bind(template_type)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let template = match current {
+        Some(current) if current.subject == subject && current.html == html
+            && name.is_none_or(|name| name == current.name) => return Ok(Some(current)),
+        Some(current) => {
+            sqlx::query_as::<_, EmailTemplate>(
+                "UPDATE email_templates
+                 SET name = COALESCE($2, name), subject = $3, html = $4,
+                     version = version + 1, updated_at = NOW()
+                 WHERE template_type = $1
+                 RETURNING *"
+            )
+            .bind(template_type)
+            .bind(name.filter(|name| *name != current.name))
+            .bind(subject)
+            .bind(html)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+        None => {
+            let Some(name) = name else {
+                return Ok(None);
+            };
+            sqlx::query_as::<_, EmailTemplate>(
+                "INSERT INTO email_templates (template_type, name, subject, html, version)
+                 VALUES ($1, $2, $3, $4, 1)
+                 RETURNING *"
+            )
+            .bind(template_type)
+            .bind(name)
+            .bind(subject)
+            .bind(html)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+    };
+
+    sqlx::query(
+        "INSERT INTO email_template_versions (template_type, version, subject, html)
+         VALUES ($1, $2, $3, $4)"
+    )
+    .bind(template_type)
+    .bind(template.version)
+    .bind(subject)
+    .bind(html)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(template))
+}
+
+// Create or update a template. Syntax errors are returned as text so the
+// editor can show them.
+async fn admin_save_email_template(
+    State(state): State<AppState>,
+    Path(template_type): Path<String>,
+    Json(req): Json<SaveEmailTemplateRequest>,
+) -> Result<Json<EmailTemplate>, (StatusCode, String)> {
+    if !valid_template_key(&template_type) {
+        return Err((StatusCode::BAD_REQUEST, "Template type may only contain a-z, 0-9 and _".to_string()));
+    }
+    if req.subject.trim().is_empty() || req.html.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Subject and body are required".to_string()));
+    }
+    template_store::validate_source(&req.subject, &req.html)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let name = req.name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+    let template = save_template_version(&state.db, &template_type, name, &req.subject, &req.html)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save email template: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save template".to_string())
+        })?
+        .ok_or((StatusCode::BAD_REQUEST, "A name is required for a new template".to_string()))?;
+
+    Ok(Json(template))
+}
+
+// Restore an earlier version. The restored content becomes a new version so
+// the history is never rewritten.
+async fn admin_rollback_email_template(
+    State(state): State<AppState>,
+    Path(template_type): Path<String>,
+    Json(req): Json<RollbackEmailTemplateRequest>,
+) -> Result<Json<EmailTemplate>, StatusCode> {
+    let version = sqlx::query_as::<_, EmailTemplateVersion>(
+        "SELECT * FROM email_template_versions WHERE template_type = $1 AND version = $2"
+    )
+    .bind(&template_type)
+    .bind(req.version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let template = save_template_version(&state.db, &template_type, None, &version.subject, &version.html)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to roll back email template: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(template))
+}
+
+async fn admin_list_template_variables(
+    State(state): State<AppState>,
+) -> Result<Json<EmailTemplateVariables>, StatusCode> {
+    let wedding = template_store::load_variables(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(EmailTemplateVariables {
+        invite: template_store::INVITE_VARIABLES.to_vec(),
+        wedding,
+    }))
+}
+
+// Add or change wedding-wide variables
+async fn admin_update_template_variables(
+    State(state): State<AppState>,
+    Json(variables): Json<Vec<EmailTemplateVariable>>,
+) -> Result<Json<EmailTemplateVariables>, StatusCode> {
+    if variables.iter().any(|v| !valid_template_key(&v.name)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tx = state.db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    for variable in &variables {
+        sqlx::query(
+            "INSERT INTO email_template_variables (name, value, description)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (name) DO UPDATE
+             SET value = EXCLUDED.value,
+                 description = COALESCE(EXCLUDED.description, email_template_variables.description),
+                 updated_at = NOW()"
+        )
+        .bind(&variable.name)
+        .bind(&variable.value)
+        .bind(&variable.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save template variable: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    admin_list_template_variables(State(state)).await
+}
+
+// ============ CALENDAR ROUTES ============
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    /// Invite code; without one only events open to everyone are included
+    pub code: Option<String>,
+}
+
+// Download the wedding events as an .ics file
+async fn public_calendar(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl axum::response::IntoResponse, StatusCode> {
+    let events = match query.code.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(code) => {
+            let invite_id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM invites WHERE unique_code = $1")
+                .bind(code)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                .ok_or(StatusCode::NOT_FOUND)?;
+            load_invite_events(&state.db, invite_id).await
+        }
+        None => sqlx::query_as::<_, Event>(
+            "SELECT * FROM events WHERE invite_all = true ORDER BY display_order, starts_at"
+        )
+        .fetch_all(&state.db)
+        .await,
+    }
+    .map_err(|e| {
+        tracing::error!("Failed to load calendar events: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let wedding = template_store::load_variables(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let frontend_url = std::env::var("FRONTEND_URL")
+        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
+    let ics = calendar::ics(&events, template_store::couple(&wedding), &frontend_url, time::OffsetDateTime::now_utc());
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("text/calendar; charset=utf-8")),
+            (
+                header::CONTENT_DISPOSITION,
+                axum::http::HeaderValue::from_str(&format!("attachment; filename=\"{}\"", calendar::ICS_FILENAME))
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+            ),
+        ],
+        ics,
+    ))
+}
+
+// ============ TRACKING ROUTES ============
+
+/// How far a webhook's timestamp may be from now before it is refused as a
+/// replay
+const WEBHOOK_TOLERANCE: time::Duration = time::Duration::minutes(5);
+
+/// Check a Resend (Svix) webhook signature: HMAC-SHA256 over
+/// "{id}.{timestamp}.{body}" with the base64 secret after "whsec_". The
+/// timestamp must be within five minutes of `now`.
+fn verify_webhook_signature(
+    secret: &str,
+    id: &str,
+    timestamp: &str,
+    body: &str,
+    signatures: &str,
+    now: time::OffsetDateTime,
+) -> bool {
+    use base64::Engine;
+    use hmac::{Hmac, Mac};
+
+    let Some(sent_at) = timestamp
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| time::OffsetDateTime::from_unix_timestamp(seconds).ok())
+    else {
+        return false;
+    };
+    if (now - sent_at).abs() > WEBHOOK_TOLERANCE {
+        return false;
+    }
+
+    let engine = base64::engine::general_purpose::STANDARD;
+    let Ok(key) = engine.decode(secret.trim_start_matches("whsec_")) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<sha2::Sha256>::new_from_slice(&key) else {
+        return false;
+    };
+    mac.update(format!("{}.{}.{}", id, timestamp, body).as_bytes());
+
+    signatures
+        .split_whitespace()
+        .filter_map(|s| s.strip_prefix("v1,"))
+        .filter_map(|s| engine.decode(s).ok())
+        .any(|signature| mac.clone().verify_slice(&signature).is_ok())
+}
+
+// Resend delivery events. Events are matched to the delivery log by the
+// provider message id; older sends are matched by their campaign_id and
+// invite_id tags.
+async fn resend_webhook(
+    State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    body: String,
+) -> Result<StatusCode, StatusCode> {
+    let secret = std::env::var("RESEND_WEBHOOK_SECRET").map_err(|_| {
+        tracing::error!("RESEND_WEBHOOK_SECRET environment variable not set");
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or("");
+    if !verify_webhook_signature(
+        &secret,
+        header("svix-id"),
+        header("svix-timestamp"),
+        &body,
+        header("svix-signature"),
+        time::OffsetDateTime::now_utc(),
+    ) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let event: serde_json::Value = serde_json::from_str(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let event_type = event["type"].as_str().unwrap_or_default();
+    let message_id = event["data"]["email_id"].as_str();
+
+    if event_type == "email.received" {
+        if let Some(email) = inbound::from_resend_event(&event["data"]).await {
+            receive_reply(&state.db, &email).await?;
+        }
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    // Advance the delivery log entry; a late event never moves it backwards
+    if let (Some(status), Some(message_id)) = (delivery_status_for_event(event_type), message_id) {
+        sqlx::query(
+            "UPDATE email_sends
+             SET status = $2, status_updated_at = NOW()
+             WHERE provider_message_id = $1
+               AND array_position($3::text[], status) < array_position($3::text[], $2::text)"
+        )
+        .bind(message_id)
+        .bind(status)
+        .bind(DELIVERY_STATUSES)
+        .execute(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update delivery status for {}: {}", message_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+
+    let reason = match event_type {
+        "email.opened" => None,
+        "email.bounced" => Some(SUPPRESS_BOUNCE),
+        "email.complained" => Some(SUPPRESS_COMPLAINT),
+        _ => return Ok(StatusCode::NO_CONTENT),
+    };
+
+    // Stop emailing addresses that hard-bounced or marked us as spam. The
+    // delivery log above already records every bounce.
+    if let Some(reason) = reason {
+        let detail = event["data"]["bounce"]["message"].as_str()
+            .or_else(|| event["data"]["subject"].as_str());
+        let addresses = if reason == SUPPRESS_BOUNCE {
+            let bounced = permanent_bounce_address(&event["data"]);
+            if bounced.is_none() {
+                tracing::warn!("Bounce for {:?} not suppressed: not permanent or recipient unknown", message_id);
+            }
+            bounced.into_iter().collect()
+        } else {
+            event["data"]["to"].as_array()
+                .map(|to| to.iter().filter_map(|a| a.as_str()).collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+        for email in addresses {
+            suppression::suppress(&state.db, email, reason, detail, None)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to record {} for {}: {}", reason, email, e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            tracing::warn!("Suppressed {} after {}", email, reason);
+        }
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let opened = sqlx::query(
+        "UPDATE email_sends
+         SET opened_at = COALESCE(opened_at, NOW()), opened_count = opened_count + 1
+         WHERE provider_message_id = $1"
+    )
+    .bind(message_id)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .rows_affected();
+
+    // Sends from before the delivery log have no message id; match their tags
+    let tag = |name: &str| event["data"]["tags"][name].as_str().and_then(|v| Uuid::parse_str(v).ok());
+    if let (0, Some(campaign_id), Some(invite_id)) = (opened, tag("campaign_id"), tag("invite_id")) {
+        sqlx::query(
+            "UPDATE email_sends
+             SET opened_at = COALESCE(opened_at, NOW()), opened_count = opened_count + 1
+             WHERE campaign_id = $1 AND invite_id = $2"
+        )
+        .bind(campaign_id)
+        .bind(invite_id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Generic inbound-email webhook for parse services that post the message as
+// JSON. Authenticated with the X-Inbound-Secret header.
+async fn inbound_email_webhook(
+    State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    Json(email): Json<InboundEmailRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let secret = std::env::var("INBOUND_EMAIL_SECRET").map_err(|_| {
+        tracing::error!("INBOUND_EMAIL_SECRET environment variable not set");
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
+    let provided = headers.get("x-inbound-secret").and_then(|v| v.to_str().ok()).unwrap_or("");
+    if !secrets_match(&secret, provided) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    receive_reply(&state.db, &email).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Compare secrets without returning early on the first differing byte.
+fn secrets_match(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected.bytes().zip(provided.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Store an inbound email as a guest reply and forward it if the couple
+/// asked for that. Our own messages looping back are ignored.
+async fn receive_reply(db: &PgPool, email: &InboundEmailRequest) -> Result<(), StatusCode> {
+    let from_email = std::env::var("FROM_EMAIL").unwrap_or_default();
+    if inbound::parse_sender(&email.from).is_some_and(|(_, address)| address.eq_ignore_ascii_case(from_email.trim())) {
+        return Ok(());
+    }
+
+    let reply = inbound::record_reply(db, email).await.map_err(|e| {
+        tracing::error!("Failed to store reply from {}: {}", email.from, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if let Some(reply) = reply {
+        tracing::info!("Received reply from {}", reply.from_email);
+        tokio::spawn(notifications::forward_reply(db.clone(), reply));
+    }
+    Ok(())
+}
+
+/// The address to suppress for a bounce event: only permanent bounces count,
+/// and only when the bounced recipient is known. That is the sole recipient,
+/// or the one the bounce message names.
+fn permanent_bounce_address(data: &serde_json::Value) -> Option<&str> {
+    if data["bounce"]["type"].as_str() != Some("Permanent") {
+        return None;
+    }
+    let recipients: Vec<&str> = data["to"].as_array()?.iter().filter_map(|to| to.as_str()).collect();
+    if let [only] = recipients.as_slice() {
+        return Some(only);
+    }
+    let message = data["bounce"]["message"].as_str()?.to_lowercase();
+    let mut named = recipients.into_iter().filter(|to| message.contains(&to.trim().to_lowercase()));
+    match (named.next(), named.next()) {
+        (Some(address), None) => Some(address),
+        _ => None,
+    }
+}
+
+/// The delivery status a Resend event moves an email to.
+fn delivery_status_for_event(event_type: &str) -> Option<&'static str> {
+    match event_type {
+        "email.sent" => Some(DELIVERY_SENT),
+        "email.delivery_delayed" => Some(DELIVERY_DELAYED),
+        "email.delivered" => Some(DELIVERY_DELIVERED),
+        "email.bounced" => Some(DELIVERY_BOUNCED),
+        "email.complained" => Some(DELIVERY_COMPLAINED),
+        _ => None,
+    }
+}
+
+// ============ REGISTRY PUBLIC ROUTES ============
+
+// List all categories with their items (public)
+async fn public_list_categories(State(state): State<AppState>) -> Result<Json<Vec<CategoryWithItems>>, StatusCode> {
+    let categories = sqlx::query_as::<_, HoneymoonCategory>(
+        "SELECT * FROM honeymoon_categories ORDER BY display_order, name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut result = Vec::new();
+    for category in categories {
+        let items = sqlx::query_as::<_, HoneymoonItem>(
+            "SELECT * FROM honeymoon_items WHERE category_id = $1 ORDER BY display_order, name"
+        )
+        .bind(category.id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        result.push(CategoryWithItems {
+            category,
+            items,
+        });
+    }
+
+    // Also add items without a category
+    let uncategorized_items = sqlx::query_as::<_, HoneymoonItem>(
+        "SELECT * FROM honeymoon_items WHERE category_id IS NULL ORDER BY display_order, name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if !uncategorized_items.is_empty() {
+        result.push(CategoryWithItems {
+            category: HoneymoonCategory {
+                id: Uuid::nil(),
+                name: "Other".to_string(),
+                display_order: 9999,
+                created_at: None,
+            },
+            items: uncategorized_items,
+        });
+    }
+
+    Ok(Json(result))
+}
+
+// Get single item with public contributions
+async fn public_get_item(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ItemWithContributions>, StatusCode> {
+    let item = sqlx::query_as::<_, HoneymoonItem>(
+        "SELECT * FROM honeymoon_items WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    // Get all contributions for display (pending shown as if confirmed)
+    let contributions = sqlx::query_as::<_, RegistryContribution>(
+        "SELECT * FROM registry_contributions WHERE item_id = $1 AND status != 'rejected' ORDER BY created_at DESC"
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let public_contributions: Vec<PublicContribution> = contributions
+        .into_iter()
+        .map(|c| PublicContribution {
+            // Hide all contributor names on public endpoint
+            display_name: "A Generous Guest".to_string(),
+            amount: c.amount,
+            message: c.message,
+            created_at: c.created_at,
+        })
+        .collect();
+
+    Ok(Json(ItemWithContributions {
+        item,
+        contributions: public_contributions,
+    }))
+}
+
+// Create contribution (public)
+async fn public_create_contribution(
+    State(state): State<AppState>,
+    Json(req): Json<CreateContributionRequest>,
+) -> Result<Json<RegistryContribution>, StatusCode> {
+    // If item_id is provided, verify the item exists and isn't fully funded
+    if let Some(item_id) = req.item_id {
+        let item = sqlx::query_as::<_, HoneymoonItem>(
+            "SELECT * FROM honeymoon_items WHERE id = $1"
+        )
+        .bind(item_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if item.is_none() {
+            return Err(StatusCode::NOT_FOUND);
+        }
+
+        if item.unwrap().is_fully_funded {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let contribution = sqlx::query_as::<_, RegistryContribution>(
+        "INSERT INTO registry_contributions (item_id, contributor_name, contributor_email, amount, is_anonymous, message, purpose)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING *"
+    )
+    .bind(req.item_id)
+    .bind(&req.contributor_name)
+    .bind(&req.contributor_email)
+    .bind(req.amount)
+    .bind(req.is_anonymous)
+    .bind(&req.message)
+    .bind(&req.purpose)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Immediately update item's total (treat pending as confirmed for display)
+    if let Some(item_id) = req.item_id {
+        let item = sqlx::query_as::<_, HoneymoonItem>(
+            "SELECT * FROM honeymoon_items WHERE id = $1"
+        )
+        .bind(item_id)
+        .fetch_one(&state.db)
+        .await
+        .ok();
+
+        if let Some(item) = item {
+            // Sum all non-rejected contributions
+            let total: Decimal = sqlx::query_scalar::<_, Decimal>(
+                "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
+            )
+            .bind(item_id)
+            .fetch_one(&state.db)
+            .await
+            .unwrap_or(Decimal::ZERO);
+
+            let is_fully_funded = total >= item.price;
+            let _ = sqlx::query(
+                "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
+            )
+            .bind(total)
+            .bind(is_fully_funded)
+            .bind(item_id)
+            .execute(&state.db)
+            .await;
+        }
+    }
+
+    Ok(Json(contribution))
+}
+
+// ============ REGISTRY ADMIN ROUTES ============
+
+// List all categories (admin)
+async fn admin_list_categories(State(state): State<AppState>) -> Result<Json<Vec<HoneymoonCategory>>, StatusCode> {
+    let categories = sqlx::query_as::<_, HoneymoonCategory>(
+        "SELECT * FROM honeymoon_categories ORDER BY display_order, name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(categories))
+}
+
+// Create category (admin)
+async fn admin_create_category(
+    State(state): State<AppState>,
+    Json(req): Json<CreateCategoryRequest>,
+) -> Result<Json<HoneymoonCategory>, StatusCode> {
+    let display_order = req.display_order.unwrap_or(0);
+
+    let category = sqlx::query_as::<_, HoneymoonCategory>(
+        "INSERT INTO honeymoon_categories (name, display_order) VALUES ($1, $2) RETURNING *"
+    )
+    .bind(&req.name)
+    .bind(display_order)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(category))
+}
+
+// Update category (admin)
+async fn admin_update_category(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateCategoryRequest>,
+) -> Result<Json<HoneymoonCategory>, StatusCode> {
+    let display_order = req.display_order.unwrap_or(0);
+
+    let category = sqlx::query_as::<_, HoneymoonCategory>(
+        "UPDATE honeymoon_categories SET name = $1, display_order = $2 WHERE id = $3 RETURNING *"
+    )
+    .bind(&req.name)
+    .bind(display_order)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok(Json(category))
+}
+
+// Delete category (admin)
+async fn admin_delete_category(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM honeymoon_categories WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// List all items (admin)
+async fn admin_list_items(State(state): State<AppState>) -> Result<Json<Vec<HoneymoonItem>>, StatusCode> {
+    let items = sqlx::query_as::<_, HoneymoonItem>(
+        "SELECT * FROM honeymoon_items ORDER BY display_order, name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(items))
+}
+
+// Create item (admin)
+async fn admin_create_item(
+    State(state): State<AppState>,
+    Json(req): Json<CreateItemRequest>,
+) -> Result<Json<HoneymoonItem>, StatusCode> {
+    let display_order = req.display_order.unwrap_or(0);
+
+    let item = sqlx::query_as::<_, HoneymoonItem>(
+        "INSERT INTO honeymoon_items (category_id, name, description, price, display_order)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *"
+    )
+    .bind(req.category_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.price)
+    .bind(display_order)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(item))
+}
+
+// Update item (admin)
+async fn admin_update_item(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateItemRequest>,
+) -> Result<Json<HoneymoonItem>, StatusCode> {
+    let display_order = req.display_order.unwrap_or(0);
+
+    let item = sqlx::query_as::<_, HoneymoonItem>(
+        "UPDATE honeymoon_items
+         SET category_id = $1, name = $2, description = $3, price = $4, display_order = $5, updated_at = NOW()
+         WHERE id = $6
+         RETURNING *"
+    )
+    .bind(req.category_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.price)
+    .bind(display_order)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok(Json(item))
+}
+
+// Delete item (admin)
+async fn admin_delete_item(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM honeymoon_items WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Upload image for item (admin)
+async fn admin_upload_item_image(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<HoneymoonItem>, StatusCode> {
+    // Verify item exists
+    let _item = sqlx::query_as::<_, HoneymoonItem>(
+        "SELECT * FROM honeymoon_items WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    // Process the upload
+    if let Some(field) = multipart.next_field().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+        let filename = field.file_name()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "image.jpg".to_string());
+
+        // Get file extension
+        let extension = filename.rsplit('.').next().unwrap_or("jpg");
+        let allowed_extensions = ["jpg", "jpeg", "png", "webp", "gif"];
+        if !allowed_extensions.contains(&extension.to_lowercase().as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+
+        // Generate unique filename
+        let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
+        let file_path = format!("./uploads/registry/{}", new_filename);
+
+        // Save file
+        let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+        tokio::fs::write(&file_path, &data).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Update item with image URL
+        let image_url = format!("/uploads/registry/{}", new_filename);
+        let item = sqlx::query_as::<_, HoneymoonItem>(
+            "UPDATE honeymoon_items SET image_url = $1, updated_at = NOW() WHERE id = $2 RETURNING *"
+        )
+        .bind(&image_url)
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        return Ok(Json(item));
+    }
+
+    Err(StatusCode::BAD_REQUEST)
+}
+
+// List all contributions (admin)
+async fn admin_list_contributions(State(state): State<AppState>) -> Result<Json<Vec<RegistryContribution>>, StatusCode> {
+    let contributions = sqlx::query_as::<_, RegistryContribution>(
+        "SELECT * FROM registry_contributions ORDER BY created_at DESC"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(contributions))
+}
+
+// Update contribution status (admin)
+async fn admin_update_contribution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateContributionRequest>,
+) -> Result<Json<RegistryContribution>, StatusCode> {
+    // Get the contribution first to check item_id
+    let contribution = sqlx::query_as::<_, RegistryContribution>(
+        "SELECT * FROM registry_contributions WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    let confirmed_at = if req.status == "confirmed" {
         Some(time::OffsetDateTime::now_utc())
     } else {
         None
     };
 
-    // Update the contribution
-    let updated_contribution = sqlx::query_as::<_, RegistryContribution>(
-        "UPDATE registry_contributions SET status = $1, confirmed_at = $2 WHERE id = $3 RETURNING *"
+    // Update the contribution
+    let updated_contribution = sqlx::query_as::<_, RegistryContribution>(
+        "UPDATE registry_contributions SET status = $1, confirmed_at = $2 WHERE id = $3 RETURNING *"
+    )
+    .bind(&req.status)
+    .bind(confirmed_at)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Always recalculate totals when status changes (include all non-rejected)
+    if let Some(item_id) = contribution.item_id {
+
+        // Recalculate total from all non-rejected contributions
+        let total: Decimal = sqlx::query_scalar::<_, Decimal>(
+            "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
+        )
+        .bind(item_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Get item price to check if fully funded
+        let item = sqlx::query_as::<_, HoneymoonItem>(
+            "SELECT * FROM honeymoon_items WHERE id = $1"
+        )
+        .bind(item_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let is_fully_funded = total >= item.price;
+
+        // Update item
+        sqlx::query(
+            "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
+        )
+        .bind(total)
+        .bind(is_fully_funded)
+        .bind(item_id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(Json(updated_contribution))
+}
+
+// Delete contribution (admin)
+async fn admin_delete_contribution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    // Get contribution first to update item totals if needed
+    let contribution = sqlx::query_as::<_, RegistryContribution>(
+        "SELECT * FROM registry_contributions WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    sqlx::query("DELETE FROM registry_contributions WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Update item totals if this was a non-rejected contribution
+    if let Some(contribution) = contribution {
+        if let Some(item_id) = contribution.item_id.filter(|_| contribution.status != "rejected") {
+            let total: Decimal = sqlx::query_scalar::<_, Decimal>(
+                "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
+            )
+            .bind(item_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let item = sqlx::query_as::<_, HoneymoonItem>(
+                "SELECT * FROM honeymoon_items WHERE id = $1"
+            )
+            .bind(item_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let is_fully_funded = total >= item.price;
+
+            sqlx::query(
+                "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
+            )
+            .bind(total)
+            .bind(is_fully_funded)
+            .bind(item_id)
+            .execute(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ PUBLIC RSVP ROUTES ============
+
+// Look up invite by code for RSVP
+async fn rsvp_lookup(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(code): Path<String>,
+) -> Result<Json<InviteRsvpResponse>, StatusCode> {
+    // Rate limit: 10 lookups per IP per 60 seconds
+    let ip = addr.ip().to_string();
+    if !state.rsvp_limiter.check(&ip, 10, 60).await {
+        tracing::warn!("RSVP rate limit exceeded for IP: {}", ip);
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    // Find invite by unique code
+    let invite = sqlx::query_as::<_, Invite>(
+        "SELECT * FROM invites WHERE unique_code = $1"
+    )
+    .bind(&code)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let invite = match invite {
+        Some(inv) => inv,
+        None => {
+            // Add a small delay on failed lookups to slow brute-force
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+
+    // Get guests for this invite
+    let guests = sqlx::query_as::<_, Guest>(
+        "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
+    )
+    .bind(invite.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if guests.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Get existing RSVPs for guests in this invite
+    let guest_ids: Vec<Uuid> = guests.iter().map(|g| g.id).collect();
+    let rsvps = sqlx::query_as::<_, Rsvp>(
+        "SELECT * FROM rsvps WHERE guest_id = ANY($1)"
+    )
+    .bind(&guest_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let already_responded = !rsvps.is_empty();
+
+    let menu = load_menu(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let meal_selections = sqlx::query_as::<_, GuestMealSelection>(
+        "SELECT guest_id, course_id, option_id FROM rsvp_meal_selections WHERE guest_id = ANY($1)"
+    )
+    .bind(&guest_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let questions = load_active_questions(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let answers = sqlx::query_as::<_, RsvpAnswer>(
+        "SELECT * FROM rsvp_answers WHERE invite_id = $1"
+    )
+    .bind(invite.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let events = load_invite_events(&state.db, invite.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let event_responses = sqlx::query_as::<_, GuestEventResponse>(
+        "SELECT guest_id, event_id, attending FROM rsvp_event_responses WHERE guest_id = ANY($1)"
+    )
+    .bind(&guest_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(InviteRsvpResponse {
+        invite: InviteWithGuests {
+            invite,
+            guests,
+        },
+        rsvps,
+        already_responded,
+        menu,
+        meal_selections,
+        dietary_options: dietary::DIETARY_OPTIONS,
+        questions,
+        answers,
+        events,
+        event_responses,
+    }))
+}
+
+/// Minimum Jaro-Winkler similarity for a typed name to count as a match
+const NAME_MATCH_THRESHOLD: f64 = 0.92;
+
+/// Lowercase, strip punctuation and collapse whitespace so "O'Brien,  Pat"
+/// and "obrien pat" compare equal.
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase()
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_postal_code(code: &str) -> String {
+    code.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_uppercase()
+}
+
+fn names_match(typed: &str, on_file: &str) -> bool {
+    let (typed, on_file) = (normalize_name(typed), normalize_name(on_file));
+    !typed.is_empty() && strsim::jaro_winkler(&typed, &on_file) >= NAME_MATCH_THRESHOLD
+}
+
+// Find an invite by guest name plus email or postal code. Every miss (no
+// match, ambiguous match, missing second factor) looks the same to the caller.
+async fn rsvp_find(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(req): Json<FindInviteRequest>,
+) -> Result<Json<FindInviteResponse>, StatusCode> {
+    // Stricter than code lookups: 5 attempts per IP per 15 minutes
+    let ip = addr.ip().to_string();
+    if !state.rsvp_limiter.check(&format!("find:{}", ip), 5, 15 * 60).await {
+        tracing::warn!("Invite find rate limit exceeded for IP: {}", ip);
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let email = req.email.as_deref().map(str::trim).filter(|e| !e.is_empty());
+    let postal_code = req.postal_code.as_deref()
+        .map(normalize_postal_code)
+        .filter(|p| !p.is_empty());
+
+    // Only guests whose second factor matches are ever compared by name
+    let candidates = if email.is_some() || postal_code.is_some() {
+        sqlx::query_as::<_, Guest>(
+            "SELECT * FROM guests
+             WHERE removed = false AND invite_id IS NOT NULL
+               AND (LOWER(email) = LOWER($1)
+                    OR UPPER(REGEXP_REPLACE(postal_code, '[^[:alnum:]]', '', 'g')) = $2)"
+        )
+        .bind(email)
+        .bind(&postal_code)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        Vec::new()
+    };
+
+    let mut invite_ids: Vec<Uuid> = candidates.iter()
+        .filter(|guest| names_match(&req.name, &guest.name))
+        .filter_map(|guest| guest.invite_id)
+        .collect();
+    invite_ids.sort_unstable();
+    invite_ids.dedup();
+
+    let invite = match invite_ids.as_slice() {
+        [invite_id] => sqlx::query_as::<_, Invite>("SELECT * FROM invites WHERE id = $1")
+            .bind(invite_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        _ => None,
+    };
+
+    match invite {
+        Some(invite) => Ok(Json(FindInviteResponse { code: invite.unique_code })),
+        None => {
+            // Same delay as a bad code lookup
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+}
+
+// Submit/update RSVP for an invite
+async fn rsvp_submit(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+    Json(submission): Json<InviteRsvpSubmission>,
+) -> Result<Json<Vec<Rsvp>>, StatusCode> {
+    // Find invite by unique code
+    let invite = sqlx::query_as::<_, Invite>(
+        "SELECT * FROM invites WHERE unique_code = $1"
+    )
+    .bind(&code)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let invite = match invite {
+        Some(inv) => inv,
+        None => return Err(StatusCode::NOT_FOUND),
+    };
+
+    let rsvps = apply_rsvp_submission(&state, invite, submission, RSVP_SOURCE_GUEST_LINK, None).await?;
+
+    Ok(Json(rsvps))
+}
+
+/// Validate and save a whole-invite RSVP submission, then send the
+/// confirmation and couple notifications. Shared by the guest link and
+/// admin-entered RSVPs; only the source and who entered it differ.
+async fn apply_rsvp_submission(
+    state: &AppState,
+    invite: Invite,
+    mut submission: InviteRsvpSubmission,
+    source: &str,
+    entered_by: Option<&str>,
+) -> Result<Vec<Rsvp>, StatusCode> {
+    // Verify all guest_ids belong to this invite
+    let invite_guest_ids: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM guests WHERE invite_id = $1 AND removed = false"
+    )
+    .bind(invite.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for entry in &submission.guests {
+        if !invite_guest_ids.contains(&entry.guest_id) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Dietary codes must come from the standard list
+    let unknown_tag = submission.guests.iter()
+        .flat_map(|entry| entry.dietary_tags.iter().flatten())
+        .find(|tag| dietary::find(tag).is_none());
+    if let Some(tag) = unknown_tag {
+        tracing::warn!("Rejected unknown dietary code: {}", tag);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Meal choices must come from the current menu. Guests who leave out
+    // their selections keep the ones already stored.
+    let menu = load_menu(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let stored_meals = sqlx::query_as::<_, GuestMealSelection>(
+        "SELECT guest_id, course_id, option_id FROM rsvp_meal_selections WHERE guest_id = ANY($1)"
+    )
+    .bind(&invite_guest_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    for entry in &submission.guests {
+        let stored_courses: Vec<Uuid> = stored_meals.iter()
+            .filter(|s| s.guest_id == entry.guest_id)
+            .map(|s| s.course_id)
+            .collect();
+        if let Err(e) = validate_meal_selections(&menu, entry, &stored_courses) {
+            tracing::warn!("Rejected meal selections for guest {}: {}", entry.guest_id, e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Event responses must be for events this invite was asked to
+    let events = load_invite_events(&state.db, invite.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    for entry in submission.guests.iter_mut() {
+        if let Err(e) = resolve_event_attendance(&events, entry) {
+            tracing::warn!("Rejected event responses for guest {}: {}", entry.guest_id, e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Answers to custom questions are checked and normalized against the
+    // active question set; required questions apply to attending guests.
+    // Answers left out of the submission keep their stored values.
+    let questions = load_active_questions(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let stored_answers = sqlx::query_as::<_, (Option<Uuid>, Uuid, serde_json::Value)>(
+        "SELECT guest_id, question_id, value FROM rsvp_answers WHERE invite_id = $1"
+    )
+    .bind(invite.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let any_attending = submission.guests.iter().any(|g| g.attending);
+    let normalized = submission.guests.iter_mut()
+        .map(|entry| {
+            let (guest_id, attending) = (entry.guest_id, entry.attending);
+            (entry.answers.as_deref_mut().unwrap_or_default(), QUESTION_SCOPE_GUEST, Some(guest_id), attending)
+        })
+        .chain(std::iter::once((
+            submission.answers.as_deref_mut().unwrap_or_default(),
+            QUESTION_SCOPE_INVITE,
+            None,
+            any_attending,
+        )))
+        .try_for_each(|(answers, scope, guest_id, attending)| {
+            let stored: Vec<QuestionAnswer> = stored_answers.iter()
+                .filter(|(g, _, _)| *g == guest_id)
+                .map(|(_, question_id, value)| QuestionAnswer { question_id: *question_id, value: value.clone() })
+                .collect();
+            normalize_answers(&questions, scope, answers, &stored, attending)
+        });
+    if let Err(e) = normalized {
+        tracing::warn!("Rejected RSVP answers for invite {}: {}", invite.unique_code, e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Upsert RSVPs for each guest, recording a revision for each submission
+    let mut tx = state.db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut result_rsvps = Vec::new();
+    let mut changed = false;
+    let mut confirm = false;
+    for entry in &submission.guests {
+        let (rsvp, changes) = save_guest_rsvp(&mut tx, invite.id, entry, source, entered_by)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to save RSVP for guest {}: {}", entry.guest_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        changed |= !changes.is_empty();
+        confirm |= changes.iter().any(|c| CONFIRMATION_FIELDS.contains(&c.field.as_str()));
+        result_rsvps.push(rsvp);
+    }
+    if let Some(answers) = &submission.answers {
+        save_answers(&mut tx, invite.id, None, answers)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to save answers for invite {}: {}", invite.unique_code, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+    let invite_id = invite.id;
+
+    // Summarize the whole invite for the confirmation email before committing
+    let confirmation = if confirm {
+        let summary = rsvp_confirmation_summary(&mut tx, invite)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to build RSVP confirmation: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        Some(summary)
+    } else {
+        None
+    };
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Email in the background so a slow or failing send never fails the RSVP
+    if changed {
+        tokio::spawn(notifications::notify_rsvp_change(state.db.clone(), invite_id));
+    }
+    if let Some((invite, summaries)) = confirmation {
+        tokio::spawn(send_rsvp_confirmation(state.db.clone(), invite, summaries));
+    }
+
+    Ok(result_rsvps)
+}
+
+/// RSVP fields listed in the confirmation email; changing only other fields
+/// (like the message to the couple) does not send a new confirmation.
+const CONFIRMATION_FIELDS: &[&str] = &[
+    "attending",
+    "dietary_restrictions",
+    "dietary_tags",
+    "meal_selections",
+    "events",
+    "song_requests",
+];
+
+/// Current answers for every guest on an invite, as listed in the
+/// confirmation email.
+async fn rsvp_confirmation_summary(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    invite: Invite,
+) -> Result<(InviteWithGuests, Vec<RsvpSummary>), sqlx::Error> {
+    let guests = sqlx::query_as::<_, Guest>(
+        "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
+    )
+    .bind(invite.id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut summaries = Vec::with_capacity(guests.len());
+    for guest in &guests {
+        let rsvp = sqlx::query_as::<_, Rsvp>("SELECT * FROM rsvps WHERE guest_id = $1")
+            .bind(guest.id)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        let mut dietary: Vec<String> = rsvp.iter()
+            .flat_map(|r| r.dietary_tags.iter().map(|tag| dietary::label(tag).to_string()))
+            .collect();
+        dietary.extend(
+            rsvp.as_ref()
+                .and_then(|r| r.dietary_restrictions.as_deref())
+                .map(str::trim)
+                .filter(|notes| !notes.is_empty())
+                .map(str::to_string),
+        );
+
+        summaries.push(RsvpSummary {
+            name: guest.name.clone(),
+            attending: rsvp.as_ref().map(|r| r.attending),
+            events: event_labels(tx, guest.id).await?,
+            meals: meal_selection_labels(tx, guest.id).await?,
+            dietary,
+            song_requests: rsvp.and_then(|r| r.song_requests).filter(|s| !s.trim().is_empty()),
+        });
+    }
+
+    Ok((InviteWithGuests { invite, guests }, summaries))
+}
+
+async fn get_or_create_rsvp_confirmation_campaign(db: &PgPool) -> Result<EmailCampaign, String> {
+    sqlx::query_as::<_, EmailCampaign>(
+        "INSERT INTO email_campaigns (name, subject, template_type)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (template_type) WHERE template_type = 'rsvp_confirmation'
+         DO UPDATE SET template_type = EXCLUDED.template_type
+         RETURNING *"
+    )
+    .bind(RSVP_CONFIRMATION_NAME)
+    .bind(RSVP_CONFIRMATION_SUBJECT)
+    .bind(RSVP_CONFIRMATION_TEMPLATE)
+    .fetch_one(db)
+    .await
+    .map_err(|e| format!("Failed to create RSVP confirmation campaign: {}", e))
+}
+
+async fn send_rsvp_confirmation(db: PgPool, invite: InviteWithGuests, summaries: Vec<RsvpSummary>) {
+    let Ok(resend_api_key) = std::env::var("RESEND_API_KEY") else {
+        tracing::warn!("RESEND_API_KEY not set; skipping RSVP confirmation for invite {}", invite.invite.unique_code);
+        return;
+    };
+
+    let campaign = match get_or_create_rsvp_confirmation_campaign(&db).await {
+        Ok(campaign) => campaign,
+        Err(e) => {
+            tracing::error!("{}", e);
+            return;
+        }
+    };
+
+    let email_service = configured_email_service(&db, resend_api_key);
+    if let Err(e) = email_service.send_rsvp_confirmation(campaign.id, &invite, &summaries).await {
+        tracing::error!("Failed to send RSVP confirmation for invite {}: {}", invite.invite.unique_code, e);
+    }
+}
+
+/// Diff a guest's previous RSVP against a new submission. A first-time RSVP
+/// reports every answered field as changed from nothing.
+fn rsvp_changes(previous: Option<&Rsvp>, entry: &GuestRsvpEntry) -> Vec<RsvpFieldChange> {
+    fn text(value: &Option<String>) -> Option<serde_json::Value> {
+        value.as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| serde_json::Value::String(v.to_string()))
+    }
+
+    let before = |f: fn(&Rsvp) -> Option<serde_json::Value>| previous.and_then(f);
+    let fields = [
+        ("attending", before(|r| Some(r.attending.into())), Some(entry.attending.into())),
+        ("dietary_restrictions", before(|r| text(&r.dietary_restrictions)), text(&entry.dietary_restrictions)),
+        ("song_requests", before(|r| text(&r.song_requests)), text(&entry.song_requests)),
+        ("message", before(|r| text(&r.message)), text(&entry.message)),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| RsvpFieldChange {
+            field: field.to_string(),
+            from,
+            to,
+        })
+        .collect()
+}
+
+/// Upsert a guest's RSVP and append a revision recording where the answer
+/// came from and what changed since the previous one. Returns the changes.
+async fn save_guest_rsvp(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    invite_id: Uuid,
+    entry: &GuestRsvpEntry,
+    source: &str,
+    entered_by: Option<&str>,
+) -> Result<(Rsvp, Vec<RsvpFieldChange>), sqlx::Error> {
+    let previous = sqlx::query_as::<_, Rsvp>(
+        "SELECT * FROM rsvps WHERE guest_id = $1 FOR UPDATE"
+    )
+    .bind(entry.guest_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let mut changes = rsvp_changes(previous.as_ref(), entry);
+    let (dietary_tags, dietary_review_status) = resolve_dietary_tags(previous.as_ref(), entry);
+    let previous_tags = previous.as_ref().map(|r| r.dietary_tags.clone()).unwrap_or_default();
+    if previous_tags != dietary_tags {
+        let as_value = |tags: Vec<String>| (!tags.is_empty()).then(|| tags.into());
+        changes.push(RsvpFieldChange {
+            field: "dietary_tags".to_string(),
+            from: as_value(previous_tags),
+            to: as_value(dietary_tags.clone()),
+        });
+    }
+
+    let rsvp = sqlx::query_as::<_, Rsvp>(
+        "INSERT INTO rsvps (guest_id, invite_id, attending, dietary_restrictions, dietary_tags, dietary_review_status, song_requests, message, source, entered_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+         ON CONFLICT (guest_id) DO UPDATE SET
+            attending = EXCLUDED.attending,
+            dietary_restrictions = EXCLUDED.dietary_restrictions,
+            dietary_tags = EXCLUDED.dietary_tags,
+            dietary_review_status = EXCLUDED.dietary_review_status,
+            song_requests = EXCLUDED.song_requests,
+            message = EXCLUDED.message,
+            source = EXCLUDED.source,
+            entered_by = EXCLUDED.entered_by,
+            updated_at = NOW()
+         RETURNING *"
+    )
+    .bind(entry.guest_id)
+    .bind(invite_id)
+    .bind(entry.attending)
+    .bind(&entry.dietary_restrictions)
+    .bind(&dietary_tags)
+    .bind(dietary_review_status)
+    .bind(&entry.song_requests)
+    .bind(&entry.message)
+    .bind(source)
+    .bind(entered_by)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    sync_guestbook_message(tx, invite_id, &rsvp, entry.publish_message).await?;
+
+    if changes.iter().any(|c| c.field == "song_requests") {
+        sync_guest_songs(tx, entry.guest_id, rsvp.song_requests.as_deref()).await?;
+    }
+
+    // Declining clears any meal choices; omitted selections are left as-is
+    let replacement = if entry.attending { entry.meal_selections.as_deref() } else { Some(&[][..]) };
+    if let Some(selections) = replacement {
+        let previous_meals = meal_selection_labels(tx, entry.guest_id).await?;
+
+        sqlx::query("DELETE FROM rsvp_meal_selections WHERE guest_id = $1")
+            .bind(entry.guest_id)
+            .execute(&mut **tx)
+            .await?;
+        for selection in selections {
+            sqlx::query(
+                "INSERT INTO rsvp_meal_selections (guest_id, course_id, option_id) VALUES ($1, $2, $3)"
+            )
+            .bind(entry.guest_id)
+            .bind(selection.course_id)
+            .bind(selection.option_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        let current_meals = meal_selection_labels(tx, entry.guest_id).await?;
+        if previous_meals != current_meals {
+            let as_value = |labels: Vec<String>| (!labels.is_empty()).then(|| labels.into());
+            changes.push(RsvpFieldChange {
+                field: "meal_selections".to_string(),
+                from: as_value(previous_meals),
+                to: as_value(current_meals),
+            });
+        }
+    }
+
+    if let Some(events) = &entry.events {
+        let previous_events = event_labels(tx, entry.guest_id).await?;
+        for event in events {
+            sqlx::query(
+                "INSERT INTO rsvp_event_responses (guest_id, event_id, attending)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (guest_id, event_id) DO UPDATE SET
+                    attending = EXCLUDED.attending,
+                    updated_at = NOW()"
+            )
+            .bind(entry.guest_id)
+            .bind(event.event_id)
+            .bind(event.attending)
+            .execute(&mut **tx)
+            .await?;
+        }
+        let current_events = event_labels(tx, entry.guest_id).await?;
+        if previous_events != current_events {
+            let as_value = |labels: Vec<String>| (!labels.is_empty()).then(|| labels.into());
+            changes.push(RsvpFieldChange {
+                field: "events".to_string(),
+                from: as_value(previous_events),
+                to: as_value(current_events),
+            });
+        }
+    }
+
+    if let Some(answers) = &entry.answers {
+        let previous_answers = answer_labels(tx, entry.guest_id).await?;
+        save_answers(tx, invite_id, Some(entry.guest_id), answers).await?;
+        let current_answers = answer_labels(tx, entry.guest_id).await?;
+        if previous_answers != current_answers {
+            let as_value = |labels: Vec<String>| (!labels.is_empty()).then(|| labels.into());
+            changes.push(RsvpFieldChange {
+                field: "answers".to_string(),
+                from: as_value(previous_answers),
+                to: as_value(current_answers),
+            });
+        }
+    }
+
+    sqlx::query(
+        "INSERT INTO rsvp_revisions
+            (rsvp_id, guest_id, invite_id, attending, dietary_restrictions, dietary_tags, song_requests, message, source, entered_by, changes)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+    )
+    .bind(rsvp.id)
+    .bind(rsvp.guest_id)
+    .bind(invite_id)
+    .bind(rsvp.attending)
+    .bind(&rsvp.dietary_restrictions)
+    .bind(&rsvp.dietary_tags)
+    .bind(&rsvp.song_requests)
+    .bind(&rsvp.message)
+    .bind(source)
+    .bind(entered_by)
+    .bind(sqlx::types::Json(&changes))
+    .execute(&mut **tx)
+    .await?;
+
+    Ok((rsvp, changes))
+}
+
+/// Work out the dietary codes to store for a submission. Codes picked by the
+/// guest are kept as-is; otherwise they are suggested from the free text and
+/// flagged for review, unless an admin already reviewed the same text.
+fn resolve_dietary_tags(previous: Option<&Rsvp>, entry: &GuestRsvpEntry) -> (Vec<String>, Option<&'static str>) {
+    if let Some(tags) = &entry.dietary_tags {
+        let mut tags = tags.clone();
+        tags.sort();
+        tags.dedup();
+        return (tags, None);
+    }
+
+    let text = entry.dietary_restrictions.as_deref().map(str::trim).unwrap_or("");
+    if let Some(previous) = previous {
+        let same_text = previous.dietary_restrictions.as_deref().map(str::trim).unwrap_or("") == text;
+        if same_text && previous.dietary_review_status.as_deref() == Some(dietary::REVIEW_DONE) {
+            return (previous.dietary_tags.clone(), Some(dietary::REVIEW_DONE));
+        }
+    }
+
+    if text.is_empty() {
+        (Vec::new(), None)
+    } else {
+        (dietary::suggest_tags(text), Some(dietary::REVIEW_PENDING))
+    }
+}
+
+/// Suggest dietary codes for RSVPs that only have free-text restrictions,
+/// leaving them pending admin review. Safe to run repeatedly.
+pub async fn suggest_legacy_dietary_tags(db: &PgPool) -> Result<usize, sqlx::Error> {
+    #[derive(sqlx::FromRow)]
+    struct LegacyRow {
+        id: Uuid,
+        dietary_restrictions: String,
+    }
+
+    let rows = sqlx::query_as::<_, LegacyRow>(
+        "SELECT id, dietary_restrictions FROM rsvps
+         WHERE dietary_review_status IS NULL
+           AND cardinality(dietary_tags) = 0
+           AND TRIM(COALESCE(dietary_restrictions, '')) <> ''"
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in &rows {
+        sqlx::query(
+            "UPDATE rsvps SET dietary_tags = $1, dietary_review_status = $2 WHERE id = $3"
+        )
+        .bind(dietary::suggest_tags(&row.dietary_restrictions))
+        .bind(dietary::REVIEW_PENDING)
+        .bind(row.id)
+        .execute(db)
+        .await?;
+    }
+
+    Ok(rows.len())
+}
+
+/// Events shown to an invite: everything marked invite_all plus the events
+/// the invite was specifically asked to.
+pub async fn load_invite_events(db: &PgPool, invite_id: Uuid) -> Result<Vec<Event>, sqlx::Error> {
+    sqlx::query_as::<_, Event>(
+        "SELECT e.* FROM events e
+         WHERE e.invite_all = true
+            OR EXISTS (
+                SELECT 1 FROM invite_events ie
+                WHERE ie.event_id = e.id AND ie.invite_id = $1
+            )
+         ORDER BY e.display_order, e.starts_at"
+    )
+    .bind(invite_id)
+    .fetch_all(db)
+    .await
+}
+
+/// Check a guest's event responses against the invite's events. A guest who
+/// declines the wedding is recorded as declining every event.
+fn resolve_event_attendance(events: &[Event], entry: &mut GuestRsvpEntry) -> Result<(), String> {
+    if !entry.attending {
+        entry.events = Some(
+            events.iter()
+                .map(|e| EventAttendance { event_id: e.id, attending: false })
+                .collect(),
+        );
+        return Ok(());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for response in entry.events.iter().flatten() {
+        if !events.iter().any(|e| e.id == response.event_id) {
+            return Err(format!("Not invited to event {}", response.event_id));
+        }
+        if !seen.insert(response.event_id) {
+            return Err(format!("Duplicate response for event {}", response.event_id));
+        }
+    }
+
+    Ok(())
+}
+
+/// "Event: attending/declined" labels for a guest's event responses.
+async fn event_labels(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    guest_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT e.name || ': ' || CASE WHEN er.attending THEN 'attending' ELSE 'declined' END
+         FROM rsvp_event_responses er
+         INNER JOIN events e ON e.id = er.event_id
+         WHERE er.guest_id = $1
+         ORDER BY e.display_order, e.starts_at"
+    )
+    .bind(guest_id)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+async fn load_active_questions(db: &PgPool) -> Result<Vec<RsvpQuestion>, sqlx::Error> {
+    sqlx::query_as::<_, RsvpQuestion>(
+        "SELECT * FROM rsvp_questions WHERE active = true ORDER BY display_order, created_at"
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Validate and normalize a value for a question. Empty answers become
+/// `null`, which clears any stored answer.
+fn normalize_answer(question: &RsvpQuestion, value: &serde_json::Value) -> Result<serde_json::Value, String> {
+    use serde_json::Value;
+
+    let invalid = || format!("Invalid answer for \"{}\"", question.prompt);
+    let is_choice = |choice: &str| question.choices.iter().any(|c| c == choice);
+
+    match (question.question_type.as_str(), value) {
+        (_, Value::Null) => Ok(Value::Null),
+        (_, Value::String(text)) if text.trim().is_empty() => Ok(Value::Null),
+        ("yes_no", Value::Bool(_)) => Ok(value.clone()),
+        ("single_choice", Value::String(choice)) if is_choice(choice) => Ok(value.clone()),
+        ("multi_choice", Value::Array(items)) => {
+            let mut chosen = Vec::new();
+            for item in items {
+                match item.as_str() {
+                    Some(choice) if is_choice(choice) => {
+                        if !chosen.contains(&choice) {
+                            chosen.push(choice);
+                        }
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            Ok(if chosen.is_empty() { Value::Null } else { chosen.into() })
+        }
+        ("text", Value::String(text)) if text.len() <= 2000 => Ok(text.trim().into()),
+        ("number", Value::Number(_)) => Ok(value.clone()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Normalize submitted answers in place for one scope, rejecting answers to
+/// unknown or out-of-scope questions and missing required answers. Questions
+/// not in `answers` are answered by their `stored` value, if any.
+fn normalize_answers(
+    questions: &[RsvpQuestion],
+    scope: &str,
+    answers: &mut [QuestionAnswer],
+    stored: &[QuestionAnswer],
+    attending: bool,
+) -> Result<(), String> {
+    for answer in answers.iter_mut() {
+        let question = questions.iter()
+            .find(|q| q.id == answer.question_id && q.scope == scope)
+            .ok_or_else(|| format!("Unknown {} question {}", scope, answer.question_id))?;
+        answer.value = normalize_answer(question, &answer.value)?;
+    }
+
+    let answered = |question: &RsvpQuestion| {
+        let answer = answers.iter()
+            .find(|a| a.question_id == question.id)
+            .or_else(|| stored.iter().find(|a| a.question_id == question.id));
+        answer.is_some_and(|a| !a.value.is_null())
+    };
+    let missing = questions.iter()
+        .filter(|q| q.scope == scope && q.required && attending)
+        .find(|q| !answered(q));
+    match missing {
+        Some(question) => Err(format!("\"{}\" is required", question.prompt)),
+        None => Ok(()),
+    }
+}
+
+/// Human-readable rendering of a stored answer for exports and history.
+fn format_answer(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+
+    match value {
+        Value::Bool(true) => "Yes".to_string(),
+        Value::Bool(false) => "No".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(format_answer).collect::<Vec<_>>().join("; "),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Store normalized answers for a guest, or for the invite when `guest_id`
+/// is `None`. Null values remove the stored answer.
+async fn save_answers(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    invite_id: Uuid,
+    guest_id: Option<Uuid>,
+    answers: &[QuestionAnswer],
+) -> Result<(), sqlx::Error> {
+    for answer in answers {
+        sqlx::query(
+            "DELETE FROM rsvp_answers
+             WHERE question_id = $1 AND invite_id = $2 AND guest_id IS NOT DISTINCT FROM $3"
+        )
+        .bind(answer.question_id)
+        .bind(invite_id)
+        .bind(guest_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if !answer.value.is_null() {
+            sqlx::query(
+                "INSERT INTO rsvp_answers (question_id, invite_id, guest_id, value) VALUES ($1, $2, $3, $4)"
+            )
+            .bind(answer.question_id)
+            .bind(invite_id)
+            .bind(guest_id)
+            .bind(&answer.value)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// "Prompt: answer" labels for a guest's custom question answers.
+async fn answer_labels(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    guest_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, serde_json::Value)>(
+        "SELECT q.prompt, a.value
+         FROM rsvp_answers a
+         INNER JOIN rsvp_questions q ON q.id = a.question_id
+         WHERE a.guest_id = $1
+         ORDER BY q.display_order, q.created_at"
+    )
+    .bind(guest_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|(prompt, value)| format!("{}: {}", prompt, format_answer(value)))
+        .collect())
+}
+
+/// "Course: Option" labels for a guest's meal choices, in menu order.
+async fn meal_selection_labels(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    guest_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>(
+        "SELECT c.name || ': ' || o.name
+         FROM rsvp_meal_selections s
+         INNER JOIN menu_courses c ON c.id = s.course_id
+         INNER JOIN menu_options o ON o.id = s.option_id
+         WHERE s.guest_id = $1
+         ORDER BY c.display_order, c.name"
+    )
+    .bind(guest_id)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+/// Check an attending guest's meal choices against the menu: each option
+/// must belong to its course, one option per course, and required courses
+/// must be answered. Without submitted selections, the courses in
+/// `stored_courses` are the guest's answers.
+fn validate_meal_selections(
+    menu: &[CourseWithOptions],
+    entry: &GuestRsvpEntry,
+    stored_courses: &[Uuid],
+) -> Result<(), String> {
+    if !entry.attending {
+        return Ok(());
+    }
+
+    let mut chosen_courses = std::collections::HashSet::new();
+    let selections = match entry.meal_selections.as_ref() {
+        Some(selections) => selections.as_slice(),
+        None => {
+            chosen_courses.extend(stored_courses.iter().copied());
+            &[]
+        }
+    };
+    for selection in selections {
+        let course = menu.iter()
+            .find(|c| c.course.id == selection.course_id)
+            .ok_or_else(|| format!("Unknown course {}", selection.course_id))?;
+        if !course.options.iter().any(|o| o.id == selection.option_id) {
+            return Err(format!("Option {} is not on the {} course", selection.option_id, course.course.name));
+        }
+        if !chosen_courses.insert(course.course.id) {
+            return Err(format!("More than one option chosen for {}", course.course.name));
+        }
+    }
+
+    match menu.iter().find(|c| c.course.required && !chosen_courses.contains(&c.course.id)) {
+        Some(missing) => Err(format!("A choice for {} is required", missing.course.name)),
+        None => Ok(()),
+    }
+}
+
+// ============ ADMIN RSVP ROUTES ============
+
+// Changes of attendance within this many days are flagged on the RSVP dashboard
+const RECENT_CHANGE_WINDOW_DAYS: i32 = 14;
+
+// Get RSVP statistics
+async fn admin_rsvp_stats(State(state): State<AppState>) -> Result<Json<RsvpStats>, StatusCode> {
+    let stats = load_rsvp_stats(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch RSVP stats: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(stats))
+}
+
+/// Guest RSVP totals plus per-event counts, shared by the dashboard and the
+/// couple's notifications.
+pub async fn load_rsvp_stats(db: &PgPool) -> Result<RsvpStats, sqlx::Error> {
+    // Total invited = all non-removed guests with invites
+    let total_invited: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM guests WHERE removed = false AND invite_id IS NOT NULL"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
+
+    // Total responded = guests with RSVPs
+    let total_responded: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
+
+    // Total attending
+    let total_attending: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND r.attending = true"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
+
+    // Total declined
+    let total_declined: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND r.attending = false"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
+
+    let total_pending = total_invited - total_responded;
+
+    // Per-event counts; invite_all events fall back to the wedding answer
+    let events = sqlx::query_as::<_, EventStats>(
+        "SELECT e.id AS event_id, e.name,
+                COUNT(g.id) AS invited,
+                COUNT(g.id) FILTER (WHERE COALESCE(er.attending, CASE WHEN e.invite_all THEN r.attending END) = true) AS attending,
+                COUNT(g.id) FILTER (WHERE COALESCE(er.attending, CASE WHEN e.invite_all THEN r.attending END) = false) AS declined,
+                COUNT(g.id) FILTER (WHERE COALESCE(er.attending, CASE WHEN e.invite_all THEN r.attending END) IS NULL) AS pending
+         FROM events e
+         LEFT JOIN guests g ON g.removed = false AND g.invite_id IS NOT NULL
+            AND (e.invite_all = true OR EXISTS (
+                SELECT 1 FROM invite_events ie WHERE ie.event_id = e.id AND ie.invite_id = g.invite_id
+            ))
+         LEFT JOIN rsvps r ON r.guest_id = g.id
+         LEFT JOIN rsvp_event_responses er ON er.guest_id = g.id AND er.event_id = e.id
+         GROUP BY e.id, e.name, e.display_order, e.starts_at
+         ORDER BY e.display_order, e.starts_at"
+    )
+    .fetch_all(db)
+    .await?;
+
+    let by_age_group = sqlx::query_as::<_, AgeGroupStats>(
+        "SELECT g.age_group,
+                COUNT(*) AS invited,
+                COUNT(r.id) FILTER (WHERE r.attending = true) AS attending,
+                COUNT(r.id) FILTER (WHERE r.attending = false) AS declined,
+                COUNT(*) FILTER (WHERE r.id IS NULL) AS pending
+         FROM guests g
+         LEFT JOIN rsvps r ON r.guest_id = g.id
+         WHERE g.removed = false AND g.invite_id IS NOT NULL
+         GROUP BY g.age_group
+         ORDER BY g.age_group"
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(RsvpStats {
+        total_invited,
+        total_responded,
+        total_attending,
+        total_declined,
+        total_pending,
+        events,
+        by_age_group,
+    })
+}
+
+/// Invited/responded/attending/declined counts grouped by a guest or invite
+/// column (or one row per tag). `group_by` is always one of the fixed
+/// expressions in `admin_rsvp_analytics`, never user input.
+async fn rsvp_breakdown(db: &PgPool, group_by: &str, from: &str) -> Result<Vec<RsvpBreakdownRow>, sqlx::Error> {
+    sqlx::query_as::<_, RsvpBreakdownRow>(&format!(
+        "SELECT COALESCE(NULLIF({group_by}, ''), 'unknown') AS key,
+                COUNT(*) AS invited,
+                COUNT(r.id) AS responded,
+                COUNT(r.id) FILTER (WHERE r.attending = true) AS attending,
+                COUNT(r.id) FILTER (WHERE r.attending = false) AS declined
+         FROM {from}
+         INNER JOIN invites i ON i.id = g.invite_id
+         LEFT JOIN rsvps r ON r.guest_id = g.id
+         WHERE g.removed = false
+         GROUP BY 1
+         ORDER BY 1"
+    ))
+    .fetch_all(db)
+    .await
+}
+
+// Breakdowns, response time series and follow-up list for the dashboard
+async fn admin_rsvp_analytics(State(state): State<AppState>) -> Result<Json<RsvpAnalytics>, StatusCode> {
+    let internal = |e: sqlx::Error| {
+        tracing::error!("Failed to build RSVP analytics: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    let by_side = rsvp_breakdown(&state.db, "g.sam_or_jonah", "guests g").await.map_err(internal)?;
+    let by_relationship = rsvp_breakdown(&state.db, "g.relationship", "guests g").await.map_err(internal)?;
+    let by_invite_type = rsvp_breakdown(&state.db, "i.invite_type", "guests g").await.map_err(internal)?;
+    let by_tag = rsvp_breakdown(&state.db, "t.tag", "guests g CROSS JOIN LATERAL UNNEST(g.tags) AS t(tag)")
+        .await
+        .map_err(internal)?;
+
+    // One row per day from the first invitation sent until today
+    let daily = sqlx::query_as::<_, DailyResponses>(
+        "WITH responses AS (
+            SELECT r.attending, r.submitted_at::date AS day
+            FROM rsvps r
+            INNER JOIN guests g ON g.id = r.guest_id
+            WHERE g.removed = false
+         ), days AS (
+            SELECT generate_series(
+                (SELECT MIN(invite_sent_at)::date FROM invites),
+                GREATEST(CURRENT_DATE, (SELECT MAX(day) FROM responses)),
+                INTERVAL '1 day'
+            )::date AS day
+         )
+         SELECT d.day,
+                COUNT(r.day) FILTER (WHERE r.day = d.day) AS responded,
+                COUNT(r.day) AS cumulative_responded,
+                COUNT(r.day) FILTER (WHERE r.attending = true) AS cumulative_attending,
+                COUNT(r.day) FILTER (WHERE r.attending = false) AS cumulative_declined
+         FROM days d
+         LEFT JOIN responses r ON r.day <= d.day
+         GROUP BY d.day
+         ORDER BY d.day"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(internal)?;
+
+    let average_days_to_respond = sqlx::query_scalar::<_, Option<f64>>(
+        "SELECT (AVG(EXTRACT(EPOCH FROM (r.submitted_at - i.invite_sent_at))) / 86400)::float8
+         FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         INNER JOIN invites i ON i.id = g.invite_id
+         WHERE g.removed = false
+           AND i.invite_sent_at IS NOT NULL
+           AND r.submitted_at >= i.invite_sent_at"
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(internal)?;
+
+    let opened_not_responded = sqlx::query_as::<_, OpenedNotResponded>(
+        "SELECT i.id AS invite_id, i.unique_code,
+                ARRAY_AGG(DISTINCT g.name) AS guest_names,
+                MAX(es.opened_at) AS last_opened_at
+         FROM invites i
+         INNER JOIN email_sends es ON es.invite_id = i.id AND es.opened_at IS NOT NULL
+         INNER JOIN guests g ON g.invite_id = i.id AND g.removed = false
+         WHERE NOT EXISTS (
+            SELECT 1 FROM rsvps r
+            INNER JOIN guests rg ON rg.id = r.guest_id
+            WHERE rg.invite_id = i.id
+         )
+         GROUP BY i.id, i.unique_code
+         ORDER BY last_opened_at DESC"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(internal)?;
+
+    Ok(Json(RsvpAnalytics {
+        by_side,
+        by_relationship,
+        by_invite_type,
+        by_tag,
+        daily,
+        average_days_to_respond,
+        opened_not_responded,
+    }))
+}
+
+// List all RSVPs grouped by invite
+async fn admin_list_rsvps(State(state): State<AppState>) -> Result<Json<Vec<AdminRsvpEntry>>, StatusCode> {
+    // Get all invites with guests
+    let invites = sqlx::query_as::<_, Invite>("SELECT * FROM invites ORDER BY created_at DESC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut entries = Vec::new();
+
+    for invite in invites {
+        let guests = sqlx::query_as::<_, Guest>(
+            "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
+        )
+        .bind(invite.id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        if guests.is_empty() {
+            continue;
+        }
+
+        let guest_ids: Vec<Uuid> = guests.iter().map(|g| g.id).collect();
+        let rsvps = sqlx::query_as::<_, Rsvp>(
+            "SELECT * FROM rsvps WHERE guest_id = ANY($1)"
+        )
+        .bind(&guest_ids)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Build RsvpWithGuest entries
+        let rsvps_with_guests: Vec<RsvpWithGuest> = rsvps.iter().map(|rsvp| {
+            let guest = guests.iter().find(|g| g.id == rsvp.guest_id);
+            RsvpWithGuest {
+                rsvp: rsvp.clone(),
+                guest_name: guest.map(|g| g.name.clone()).unwrap_or_default(),
+                guest_email: guest.map(|g| g.email.clone()).unwrap_or_default(),
+            }
+        }).collect();
+
+        // Determine status
+        let status = if rsvps.is_empty() {
+            "pending".to_string()
+        } else if rsvps.len() < guests.len() {
+            "partial".to_string()
+        } else if rsvps.iter().all(|r| r.attending) {
+            "attending".to_string()
+        } else if rsvps.iter().all(|r| !r.attending) {
+            "declined".to_string()
+        } else {
+            "partial".to_string()
+        };
+
+        // Flag guests who recently switched between attending and declining
+        let changed_mind_at = sqlx::query_scalar::<_, Option<time::OffsetDateTime>>(
+            "SELECT MAX(rr.created_at) FROM rsvp_revisions rr
+             WHERE rr.invite_id = $1
+               AND rr.created_at > NOW() - make_interval(days => $2)
+               AND rr.changes @> '[{\"field\": \"attending\"}]'
+               AND EXISTS (
+                   SELECT 1 FROM rsvp_revisions earlier
+                   WHERE earlier.guest_id = rr.guest_id AND earlier.created_at < rr.created_at
+               )"
+        )
+        .bind(invite.id)
+        .bind(RECENT_CHANGE_WINDOW_DAYS)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        entries.push(AdminRsvpEntry {
+            invite: InviteWithGuests {
+                invite: invite.clone(),
+                guests,
+            },
+            rsvps: rsvps_with_guests,
+            status,
+            changed_mind_at,
+        });
+    }
+
+    Ok(Json(entries))
+}
+
+// Create or edit an invite's RSVP on a guest's behalf (phone, paper card,
+// in person). There is no deadline here, so late answers can still be taken.
+async fn admin_submit_rsvp(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AdminRsvpSubmission>,
+) -> Result<Json<Vec<Rsvp>>, StatusCode> {
+    let entered_by = req.entered_by.trim();
+    if !ADMIN_RSVP_CHANNELS.contains(&req.channel.as_str()) || entered_by.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let invite = sqlx::query_as::<_, Invite>("SELECT * FROM invites WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let rsvps = apply_rsvp_submission(&state, invite, req.submission, &req.channel, Some(entered_by)).await?;
+
+    Ok(Json(rsvps))
+}
+
+// Get the RSVP timeline for an invite, oldest first
+async fn admin_rsvp_history(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<RsvpRevisionWithGuest>>, StatusCode> {
+    let revisions = sqlx::query_as::<_, RsvpRevisionWithGuest>(
+        "SELECT rr.*, g.name AS guest_name
+         FROM rsvp_revisions rr
+         INNER JOIN guests g ON g.id = rr.guest_id
+         WHERE rr.invite_id = $1
+         ORDER BY rr.created_at, g.name"
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch RSVP history for invite {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(revisions))
+}
+
+// Every email sent to an invite, newest first
+async fn admin_invite_emails(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EmailSendLogEntry>>, StatusCode> {
+    let sends = sqlx::query_as::<_, EmailSendLogEntry>(
+        "SELECT es.*, ec.name AS campaign_name
+         FROM email_sends es
+         LEFT JOIN email_campaigns ec ON ec.id = es.campaign_id
+         WHERE es.invite_id = $1
+         ORDER BY es.sent_at DESC"
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch emails for invite {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(sends))
+}
+
+// Every email sent to a guest's address on their invite, newest first.
+// Sends from before the delivery log have no recipients and are included.
+async fn admin_guest_emails(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<EmailSendLogEntry>>, StatusCode> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM guests WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !exists {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let sends = guest_email_log(&state.db, id).await.map_err(|e| {
+        tracing::error!("Failed to fetch emails for guest {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(sends))
+}
+
+async fn guest_email_log(db: &PgPool, guest_id: Uuid) -> Result<Vec<EmailSendLogEntry>, sqlx::Error> {
+    sqlx::query_as::<_, EmailSendLogEntry>(
+        "SELECT es.*, ec.name AS campaign_name
+         FROM guests g
+         INNER JOIN email_sends es
+            ON es.guest_id = g.id
+            OR (es.invite_id = g.invite_id AND (
+                cardinality(es.recipients) = 0
+                OR LOWER(g.email) IN (SELECT LOWER(r) FROM unnest(es.recipients) r)))
+         LEFT JOIN email_campaigns ec ON ec.id = es.campaign_id
+         WHERE g.id = $1
+         ORDER BY es.sent_at DESC"
+    )
+    .bind(guest_id)
+    .fetch_all(db)
+    .await
+}
+
+// Replies from guests, newest first. `?handled=false` lists only the ones
+// still waiting on an answer.
+async fn admin_list_replies(
+    State(state): State<AppState>,
+    Query(query): Query<ReplyQuery>,
+) -> Result<Json<Vec<EmailReplyWithGuest>>, StatusCode> {
+    let replies = sqlx::query_as::<_, EmailReplyWithGuest>(
+        "SELECT r.*, g.name AS guest_name
+         FROM email_replies r
+         LEFT JOIN guests g ON g.id = r.guest_id
+         WHERE $1::boolean IS NULL OR (r.handled_at IS NOT NULL) = $1
+         ORDER BY r.received_at DESC"
+    )
+    .bind(query.handled)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch replies: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(replies))
+}
+
+async fn admin_mark_reply_handled(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<MarkReplyHandledRequest>,
+) -> Result<Json<EmailReply>, StatusCode> {
+    let reply = sqlx::query_as::<_, EmailReply>(
+        "UPDATE email_replies
+         SET handled_at = CASE WHEN $2 THEN COALESCE(handled_at, NOW()) END
+         WHERE id = $1
+         RETURNING *"
+    )
+    .bind(id)
+    .bind(payload.handled)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update reply {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(reply))
+}
+
+// A guest's replies and the emails they were sent. Replies from the guest's
+// address that arrived before it was on file are included.
+async fn admin_guest_conversation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<GuestConversation>, StatusCode> {
+    let guest = sqlx::query_as::<_, Guest>("SELECT * FROM guests WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let replies = sqlx::query_as::<_, EmailReply>(
+        "SELECT * FROM email_replies
+         WHERE guest_id = $1 OR LOWER(from_email) = LOWER(TRIM($2))
+         ORDER BY received_at DESC"
+    )
+    .bind(id)
+    .bind(&guest.email)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch replies for guest {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let sent = guest_email_log(&state.db, id).await.map_err(|e| {
+        tracing::error!("Failed to fetch emails for guest {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(GuestConversation { guest, replies, sent }))
+}
+
+// Group guests by standard dietary code, with free-text-only answers and
+// answers awaiting review listed separately
+async fn admin_dietary_report(State(state): State<AppState>) -> Result<Json<DietaryReport>, StatusCode> {
+    let guests = sqlx::query_as::<_, DietaryGuest>(
+        "SELECT r.id AS rsvp_id, g.id AS guest_id, g.name AS guest_name, r.attending,
+                r.dietary_tags, r.dietary_restrictions, r.dietary_review_status
+         FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false
+           AND (cardinality(r.dietary_tags) > 0 OR TRIM(COALESCE(r.dietary_restrictions, '')) <> '')
+         ORDER BY g.name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to fetch dietary report: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let groups = dietary::DIETARY_OPTIONS
+        .iter()
+        .map(|option| DietaryGroup {
+            code: option.code,
+            label: option.label,
+            kind: option.kind,
+            guests: guests.iter()
+                .filter(|g| g.attending && g.dietary_tags.iter().any(|t| t == option.code))
+                .cloned()
+                .collect(),
+        })
+        .filter(|group| !group.guests.is_empty())
+        .collect();
+
+    let notes_only = guests.iter()
+        .filter(|g| g.attending && g.dietary_tags.is_empty())
+        .cloned()
+        .collect();
+    let pending_review = guests.into_iter()
+        .filter(|g| g.dietary_review_status.as_deref() == Some(dietary::REVIEW_PENDING))
+        .collect();
+
+    Ok(Json(DietaryReport {
+        groups,
+        notes_only,
+        pending_review,
+    }))
+}
+
+// Confirm or correct a guest's dietary codes (admin review)
+async fn admin_update_dietary_tags(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateDietaryTagsRequest>,
+) -> Result<Json<Rsvp>, StatusCode> {
+    if req.dietary_tags.iter().any(|tag| dietary::find(tag).is_none()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tags = req.dietary_tags;
+    tags.sort();
+    tags.dedup();
+
+    let rsvp = sqlx::query_as::<_, Rsvp>(
+        "UPDATE rsvps SET dietary_tags = $1, dietary_review_status = $2, updated_at = NOW()
+         WHERE id = $3
+         RETURNING *"
+    )
+    .bind(&tags)
+    .bind(dietary::REVIEW_DONE)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok(Json(rsvp))
+}
+
+// Re-run the keyword mapping over free-text answers that have no codes yet
+async fn admin_suggest_dietary_tags(State(state): State<AppState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let suggested = suggest_legacy_dietary_tags(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to suggest dietary tags: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(serde_json::json!({ "suggested_count": suggested })))
+}
+
+// Export RSVPs as CSV
+async fn admin_export_rsvps(State(state): State<AppState>) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], String), StatusCode> {
+    #[derive(sqlx::FromRow)]
+    struct ExportRow {
+        guest_id: Uuid,
+        invite_id: Option<Uuid>,
+        name: String,
+        email: String,
+        attending: bool,
+        dietary_restrictions: Option<String>,
+        dietary_tags: Vec<String>,
+        song_requests: Option<String>,
+        message: Option<String>,
+        source: String,
+        entered_by: Option<String>,
+        submitted_at: Option<time::OffsetDateTime>,
+    }
+
+    let rows = sqlx::query_as::<_, ExportRow>(
+        "SELECT g.id AS guest_id, g.invite_id, g.name, g.email, r.attending, r.dietary_restrictions, r.dietary_tags, r.song_requests, r.message, r.source, r.entered_by, r.submitted_at
+         FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false
+         ORDER BY g.name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Custom questions become extra columns, answered per guest or per invite
+    let questions = sqlx::query_as::<_, RsvpQuestion>(
+        "SELECT * FROM rsvp_questions ORDER BY display_order, created_at"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let answers = sqlx::query_as::<_, RsvpAnswer>("SELECT * FROM rsvp_answers")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut csv = String::from("Name,Email,Attending,Dietary,Dietary Notes,Song Requests,Message,Source,Entered By,Submitted At");
+    for question in &questions {
+        csv.push_str(&format!(",\"{}\"", question.prompt.replace('"', "\"\"")));
+    }
+    csv.push('\n');
+    for row in &rows {
+        let submitted_str = row.submitted_at.map(|t| t.to_string()).unwrap_or_default();
+        let dietary_labels: Vec<&str> = row.dietary_tags.iter().map(|t| dietary::label(t)).collect();
+        csv.push_str(&format!(
+            "\"{}\",\"{}\",{},\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\",\"{}\"",
+            row.name.replace('"', "\"\""),
+            row.email.replace('"', "\"\""),
+            if row.attending { "Yes" } else { "No" },
+            dietary_labels.join("; "),
+            row.dietary_restrictions.as_deref().unwrap_or("").replace('"', "\"\""),
+            row.song_requests.as_deref().unwrap_or("").replace('"', "\"\""),
+            row.message.as_deref().unwrap_or("").replace('"', "\"\""),
+            row.source,
+            row.entered_by.as_deref().unwrap_or("").replace('"', "\"\""),
+            submitted_str,
+        ));
+        for question in &questions {
+            let answer = answers.iter().find(|a| {
+                a.question_id == question.id
+                    && match a.guest_id {
+                        Some(guest_id) => guest_id == row.guest_id,
+                        None => Some(a.invite_id) == row.invite_id,
+                    }
+            });
+            let text = answer.map(|a| format_answer(&a.value)).unwrap_or_default();
+            csv.push_str(&format!(",\"{}\"", text.replace('"', "\"\"")));
+        }
+        csv.push('\n');
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("text/csv")),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static("attachment; filename=\"rsvps.csv\"")),
+        ],
+        csv,
+    ))
+}
+
+// ============ GUESTBOOK ROUTES ============
+
+/// Longest guestbook message accepted from the public form
+const GUESTBOOK_MAX_LEN: usize = 2000;
+/// Longest guestbook signature, the size of the author_name column
+const GUESTBOOK_AUTHOR_MAX_LEN: usize = 255;
+
+/// Keep a published RSVP message in step with the RSVP. Opting out or
+/// clearing the message removes the entry; a changed message goes back to
+/// pending so it is approved again before it shows.
+async fn sync_guestbook_message(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    invite_id: Uuid,
+    rsvp: &Rsvp,
+    publish: Option<bool>,
+) -> Result<(), sqlx::Error> {
+    let message = rsvp.message.as_deref().map(str::trim).filter(|m| !m.is_empty());
+
+    match (publish, message) {
+        (Some(false), _) | (_, None) => {
+            sqlx::query("DELETE FROM guestbook_entries WHERE rsvp_id = $1")
+                .bind(rsvp.id)
+                .execute(&mut **tx)
+                .await?;
+        }
+        (Some(true), Some(message)) => {
+            sqlx::query(
+                "INSERT INTO guestbook_entries (invite_id, guest_id, rsvp_id, author_name, message)
+                 SELECT $1, g.id, $3, g.name, $4 FROM guests g WHERE g.id = $2
+                 ON CONFLICT (rsvp_id) WHERE rsvp_id IS NOT NULL DO UPDATE SET
+                    status = CASE WHEN guestbook_entries.message = EXCLUDED.message
+                                  THEN guestbook_entries.status ELSE 'pending' END,
+                    message = EXCLUDED.message,
+                    updated_at = NOW()"
+            )
+            .bind(invite_id)
+            .bind(rsvp.guest_id)
+            .bind(rsvp.id)
+            .bind(message)
+            .execute(&mut **tx)
+            .await?;
+        }
+        (None, Some(message)) => {
+            sqlx::query(
+                "UPDATE guestbook_entries SET
+                    status = CASE WHEN message = $2 THEN status ELSE 'pending' END,
+                    message = $2,
+                    updated_at = NOW()
+                 WHERE rsvp_id = $1"
+            )
+            .bind(rsvp.id)
+            .bind(message)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Approved guestbook entries, newest first
+async fn public_guestbook(State(state): State<AppState>) -> Result<Json<Vec<PublicGuestbookEntry>>, StatusCode> {
+    let entries = sqlx::query_as::<_, PublicGuestbookEntry>(
+        "SELECT author_name, message, created_at FROM guestbook_entries
+         WHERE status = $1
+         ORDER BY created_at DESC"
+    )
+    .bind(GUESTBOOK_APPROVED)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(entries))
+}
+
+// Post a new guestbook message with an invite code; held for approval
+async fn public_create_guestbook_post(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(req): Json<CreateGuestbookPostRequest>,
+) -> Result<(StatusCode, Json<PublicGuestbookEntry>), StatusCode> {
+    let ip = addr.ip().to_string();
+    if !state.rsvp_limiter.check(&format!("guestbook:{}", ip), 5, 10 * 60).await {
+        tracing::warn!("Guestbook rate limit exceeded for IP: {}", ip);
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let message = req.message.trim();
+    if message.is_empty() || message.chars().count() > GUESTBOOK_MAX_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let invite = sqlx::query_as::<_, Invite>("SELECT * FROM invites WHERE unique_code = $1")
+        .bind(&req.code)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let guests = sqlx::query_as::<_, Guest>(
+        "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
+    )
+    .bind(invite.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Sign as the chosen guest, a name the guest typed, or the whole invite
+    let guest = match req.guest_id {
+        Some(guest_id) => Some(guests.iter().find(|g| g.id == guest_id).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let typed_name = req.author_name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+    if typed_name.is_some_and(|name| name.chars().count() > GUESTBOOK_AUTHOR_MAX_LEN) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let author_name: String = typed_name
+        .map(str::to_string)
+        .or_else(|| guest.map(|g| g.name.clone()))
+        .unwrap_or_else(|| guests.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(" & "))
+        .chars()
+        .take(GUESTBOOK_AUTHOR_MAX_LEN)
+        .collect();
+
+    let entry = sqlx::query_as::<_, PublicGuestbookEntry>(
+        "INSERT INTO guestbook_entries (invite_id, guest_id, author_name, message)
+         VALUES ($1, $2, $3, $4)
+         RETURNING author_name, message, created_at"
+    )
+    .bind(invite.id)
+    .bind(guest.map(|g| g.id))
+    .bind(&author_name)
+    .bind(message)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((StatusCode::ACCEPTED, Json(entry)))
+}
+
+// All guestbook entries for moderation (?status=pending|approved|rejected)
+async fn admin_list_guestbook(
+    State(state): State<AppState>,
+    Query(query): Query<GuestbookQuery>,
+) -> Result<Json<Vec<GuestbookEntry>>, StatusCode> {
+    let entries = sqlx::query_as::<_, GuestbookEntry>(
+        "SELECT * FROM guestbook_entries
+         WHERE $1::text IS NULL OR status = $1
+         ORDER BY created_at DESC"
+    )
+    .bind(&query.status)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(entries))
+}
+
+// Approve or reject a guestbook entry
+async fn admin_update_guestbook_status(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateGuestbookStatusRequest>,
+) -> Result<Json<GuestbookEntry>, StatusCode> {
+    if !GUESTBOOK_STATUSES.contains(&req.status.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let entry = sqlx::query_as::<_, GuestbookEntry>(
+        "UPDATE guestbook_entries SET status = $1, reviewed_at = NOW(), updated_at = NOW()
+         WHERE id = $2
+         RETURNING *"
+    )
+    .bind(&req.status)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok(Json(entry))
+}
+
+async fn admin_delete_guestbook_entry(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM guestbook_entries WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Keepsake PDF of every message: approved guestbook entries, all RSVP
+// messages and notes left with registry contributions
+async fn admin_guestbook_keepsake(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], Vec<u8>), StatusCode> {
+    let guestbook = sqlx::query_as::<_, (String, String)>(
+        "SELECT author_name, message FROM guestbook_entries
+         WHERE status = $1 AND rsvp_id IS NULL
+         ORDER BY created_at"
+    )
+    .bind(GUESTBOOK_APPROVED)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let rsvp_messages = sqlx::query_as::<_, (String, String)>(
+        "SELECT g.name, r.message FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND COALESCE(TRIM(r.message), '') <> ''
+         ORDER BY r.submitted_at"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let registry_notes = sqlx::query_as::<_, (String, String)>(
+        "SELECT CASE WHEN is_anonymous THEN 'Anonymous' ELSE COALESCE(contributor_name, 'Anonymous') END, message
+         FROM registry_contributions
+         WHERE status <> 'rejected' AND COALESCE(TRIM(message), '') <> ''
+         ORDER BY created_at"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut lines = Vec::new();
+    for (heading, messages) in [
+        ("RSVP MESSAGES", &rsvp_messages),
+        ("GUESTBOOK", &guestbook),
+        ("REGISTRY NOTES", &registry_notes),
+    ] {
+        if messages.is_empty() {
+            continue;
+        }
+        lines.push(heading.to_string());
+        lines.push(String::new());
+        for (author, message) in messages {
+            lines.extend(message.trim().lines().map(|line| format!("    {}", line)));
+            lines.push(format!("        - {}", author));
+            lines.push(String::new());
+        }
+    }
+
+    let pdf = crate::pdf::text_document("Messages for Sam & Jonah", &lines);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("application/pdf")),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static("attachment; filename=\"guestbook-keepsake.pdf\"")),
+        ],
+        pdf,
+    ))
+}
+
+// ============ SONG REQUEST ROUTES ============
+
+/// Replace a guest's parsed song requests, merging songs other guests
+/// already asked for.
+async fn sync_guest_songs(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    guest_id: Uuid,
+    song_requests: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM song_request_guests WHERE guest_id = $1")
+        .bind(guest_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for song in songs::parse_song_requests(song_requests.unwrap_or("")) {
+        let song_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO songs (song_key, artist, title)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (song_key) DO UPDATE SET
+                artist = COALESCE(songs.artist, EXCLUDED.artist),
+                updated_at = NOW()
+             RETURNING id"
+        )
+        .bind(song.key())
+        .bind(&song.artist)
+        .bind(&song.title)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO song_request_guests (song_id, guest_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+        .bind(song_id)
+        .bind(guest_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Parse song requests saved before songs were tracked. Returns the number
+/// of guests whose requests were parsed.
+pub async fn backfill_song_requests(db: &PgPool) -> Result<usize, sqlx::Error> {
+    let pending = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT r.guest_id, r.song_requests
+         FROM rsvps r
+         WHERE COALESCE(TRIM(r.song_requests), '') <> ''
+           AND NOT EXISTS (SELECT 1 FROM song_request_guests sg WHERE sg.guest_id = r.guest_id)"
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut tx = db.begin().await?;
+    for (guest_id, song_requests) in &pending {
+        sync_guest_songs(&mut tx, *guest_id, Some(song_requests)).await?;
+    }
+    tx.commit().await?;
+
+    Ok(pending.len())
+}
+
+/// Songs with how many guests asked for them, most requested first.
+async fn load_songs(db: &PgPool, status: Option<&str>) -> Result<Vec<SongWithRequests>, sqlx::Error> {
+    sqlx::query_as::<_, SongWithRequests>(
+        "SELECT s.*,
+                COUNT(g.id) AS request_count,
+                COALESCE(ARRAY_AGG(g.name ORDER BY g.name) FILTER (WHERE g.id IS NOT NULL), '{}') AS requested_by
+         FROM songs s
+         LEFT JOIN song_request_guests sg ON sg.song_id = s.id
+         LEFT JOIN guests g ON g.id = sg.guest_id AND g.removed = false
+         WHERE $1::text IS NULL OR s.status = $1
+         GROUP BY s.id
+         HAVING COUNT(g.id) > 0
+         ORDER BY COUNT(g.id) DESC, s.artist, s.title"
+    )
+    .bind(status)
+    .fetch_all(db)
+    .await
+}
+
+/// Approved songs as CSV, M3U or one-per-line text.
+fn render_playlist(songs: &[SongWithRequests], format: &PlaylistFormat) -> (&'static str, &'static str, String) {
+    let label = |s: &Song| match &s.artist {
+        Some(artist) => format!("{} - {}", artist, s.title),
+        None => s.title.clone(),
+    };
+
+    match format {
+        PlaylistFormat::Csv => {
+            let mut csv = String::from("Artist,Title,Requests,Requested By\n");
+            for entry in songs {
+                csv.push_str(&format!(
+                    "\"{}\",\"{}\",{},\"{}\"\n",
+                    entry.song.artist.as_deref().unwrap_or("").replace('"', "\"\""),
+                    entry.song.title.replace('"', "\"\""),
+                    entry.request_count,
+                    entry.requested_by.join("; ").replace('"', "\"\""),
+                ));
+            }
+            ("text/csv", "attachment; filename=\"playlist.csv\"", csv)
+        }
+        PlaylistFormat::M3u => {
+            let mut m3u = String::from("#EXTM3U\n");
+            for entry in songs {
+                let label = label(&entry.song);
+                m3u.push_str(&format!("#EXTINF:-1,{}\n{}\n", label, label));
+            }
+            ("audio/x-mpegurl", "attachment; filename=\"playlist.m3u\"", m3u)
+        }
+        PlaylistFormat::Txt => {
+            let text: String = songs.iter()
+                .map(|entry| format!("{} ({})\n", label(&entry.song), entry.request_count))
+                .collect();
+            ("text/plain; charset=utf-8", "inline", text)
+        }
+    }
+}
+
+async fn admin_list_songs(State(state): State<AppState>) -> Result<Json<Vec<SongWithRequests>>, StatusCode> {
+    let songs = load_songs(&state.db, None)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch songs: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(songs))
+}
+
+// Approve, ban or reset a song
+async fn admin_update_song_status(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateSongStatusRequest>,
+) -> Result<Json<Song>, StatusCode> {
+    if !SONG_STATUSES.contains(&req.status.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let song = sqlx::query_as::<_, Song>(
+        "UPDATE songs SET status = $1, updated_at = NOW()
+         WHERE id = $2
+         RETURNING id, artist, title, status, created_at, updated_at"
     )
     .bind(&req.status)
-    .bind(confirmed_at)
     .bind(id)
     .fetch_one(&state.db)
     .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok(Json(song))
+}
+
+// Approved songs for the DJ (?format=csv|m3u|txt)
+async fn admin_export_songs(
+    State(state): State<AppState>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], String), StatusCode> {
+    let songs = load_songs(&state.db, Some(SONG_APPROVED))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (content_type, disposition, body) = render_playlist(&songs, &query.format);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static(content_type)),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static(disposition)),
+        ],
+        body,
+    ))
+}
+
+fn playlist_share_link(token: String) -> PlaylistShareLink {
+    let api_url = std::env::var("API_URL")
+        .or_else(|_| std::env::var("FRONTEND_URL"))
+        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
+    let url = format!("{}/api/playlist/{}?format=txt", api_url.trim_end_matches('/'), token);
+    PlaylistShareLink { token, url }
+}
+
+async fn create_playlist_token(db: &PgPool) -> Result<String, sqlx::Error> {
+    let token = Uuid::new_v4().simple().to_string();
+    sqlx::query("INSERT INTO playlist_share_links (token) VALUES ($1)")
+        .bind(&token)
+        .execute(db)
+        .await?;
+    Ok(token)
+}
+
+// Current DJ share link, created on first use
+async fn admin_get_playlist_link(State(state): State<AppState>) -> Result<Json<PlaylistShareLink>, StatusCode> {
+    let existing = sqlx::query_scalar::<_, String>(
+        "SELECT token FROM playlist_share_links WHERE revoked = false ORDER BY created_at DESC LIMIT 1"
+    )
+    .fetch_optional(&state.db)
+    .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Always recalculate totals when status changes (include all non-rejected)
-    if contribution.item_id.is_some() {
-        let item_id = contribution.item_id.unwrap();
+    let token = match existing {
+        Some(token) => token,
+        None => create_playlist_token(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    };
 
-        // Recalculate total from all non-rejected contributions
-        let total: Decimal = sqlx::query_scalar::<_, Decimal>(
-            "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
-        )
-        .bind(item_id)
-        .fetch_one(&state.db)
+    Ok(Json(playlist_share_link(token)))
+}
+
+// Revoke old DJ links and issue a new one
+async fn admin_rotate_playlist_link(State(state): State<AppState>) -> Result<Json<PlaylistShareLink>, StatusCode> {
+    sqlx::query("UPDATE playlist_share_links SET revoked = true WHERE revoked = false")
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let token = create_playlist_token(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        // Get item price to check if fully funded
-        let item = sqlx::query_as::<_, HoneymoonItem>(
-            "SELECT * FROM honeymoon_items WHERE id = $1"
-        )
-        .bind(item_id)
-        .fetch_one(&state.db)
+    Ok(Json(playlist_share_link(token)))
+}
+
+// Approved songs for the DJ, readable with the share link only
+async fn public_playlist(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    Query(query): Query<PlaylistQuery>,
+) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], String), StatusCode> {
+    let valid = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM playlist_share_links WHERE token = $1 AND revoked = false)"
+    )
+    .bind(&token)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !valid {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let songs = load_songs(&state.db, Some(SONG_APPROVED))
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (content_type, disposition, body) = render_playlist(&songs, &query.format);
 
-        let is_fully_funded = total >= item.price;
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static(content_type)),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static(disposition)),
+        ],
+        body,
+    ))
+}
 
-        // Update item
-        sqlx::query(
-            "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
-        )
-        .bind(total)
-        .bind(is_fully_funded)
-        .bind(item_id)
-        .execute(&state.db)
+// ============ RSVP NOTIFICATION ROUTES ============
+
+async fn admin_get_notification_settings(
+    State(state): State<AppState>,
+) -> Result<Json<NotificationSettings>, StatusCode> {
+    let settings = notifications::load_settings(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    }
 
-    Ok(Json(updated_contribution))
+    Ok(Json(settings))
 }
 
-// Delete contribution (admin)
-async fn admin_delete_contribution(
+async fn admin_update_notification_settings(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
-    // Get contribution first to update item totals if needed
-    let contribution = sqlx::query_as::<_, RegistryContribution>(
-        "SELECT * FROM registry_contributions WHERE id = $1"
+    Json(req): Json<UpdateNotificationSettingsRequest>,
+) -> Result<Json<NotificationSettings>, StatusCode> {
+    let recipients: Vec<String> = req.recipients.iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect();
+    let valid_recipients = recipients.iter()
+        .all(|r| is_valid_email(r));
+    let valid_hour = req.digest_hour_utc.is_none_or(|h| (0..24).contains(&h));
+    if !NOTIFICATION_FREQUENCIES.contains(&req.frequency.as_str()) || !valid_recipients || !valid_hour {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let settings = sqlx::query_as::<_, NotificationSettings>(
+        "UPDATE notification_settings
+         SET recipients = $1, frequency = $2, digest_hour_utc = COALESCE($3, digest_hour_utc),
+             forward_replies = COALESCE($4, forward_replies), updated_at = NOW()
+         RETURNING recipients, frequency, digest_hour_utc, last_digest_at, updated_at, forward_replies"
     )
-    .bind(id)
-    .fetch_optional(&state.db)
+    .bind(&recipients)
+    .bind(&req.frequency)
+    .bind(req.digest_hour_utc)
+    .bind(req.forward_replies)
+    .fetch_one(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    sqlx::query("DELETE FROM registry_contributions WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
+    Ok(Json(settings))
+}
+
+// What the next digest would contain
+async fn admin_preview_digest(State(state): State<AppState>) -> Result<Json<RsvpDigest>, StatusCode> {
+    let settings = notifications::load_settings(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let digest = notifications::pending_digest(&state.db, &settings)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
 
-    // Update item totals if this was a non-rejected contribution
-    if let Some(contribution) = contribution {
-        if contribution.status != "rejected" && contribution.item_id.is_some() {
-            let item_id = contribution.item_id.unwrap();
+    Ok(Json(digest))
+}
 
-            let total: Decimal = sqlx::query_scalar::<_, Decimal>(
-                "SELECT COALESCE(SUM(amount), 0) FROM registry_contributions WHERE item_id = $1 AND status != 'rejected'"
-            )
-            .bind(item_id)
-            .fetch_one(&state.db)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+// Send the digest now instead of waiting for the scheduled time
+async fn admin_send_digest(State(state): State<AppState>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = notifications::load_settings(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let updates = notifications::send_digest(&state.db, &settings)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to send RSVP digest: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
 
-            let item = sqlx::query_as::<_, HoneymoonItem>(
-                "SELECT * FROM honeymoon_items WHERE id = $1"
-            )
-            .bind(item_id)
-            .fetch_one(&state.db)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::json!({ "updates": updates })))
+}
 
-            let is_fully_funded = total >= item.price;
+// ============ EVENT ROUTES ============
 
-            sqlx::query(
-                "UPDATE honeymoon_items SET total_contributed = $1, is_fully_funded = $2, updated_at = NOW() WHERE id = $3"
-            )
-            .bind(total)
-            .bind(is_fully_funded)
-            .bind(item_id)
-            .execute(&state.db)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        }
-    }
+// List all events (admin)
+async fn admin_list_events(State(state): State<AppState>) -> Result<Json<Vec<Event>>, StatusCode> {
+    let events = sqlx::query_as::<_, Event>(
+        "SELECT * FROM events ORDER BY display_order, starts_at"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(Json(events))
 }
 
-// ============ PUBLIC RSVP ROUTES ============
+// Create event (admin)
+async fn admin_create_event(
+    State(state): State<AppState>,
+    Json(req): Json<CreateEventRequest>,
+) -> Result<Json<Event>, StatusCode> {
+    let event = sqlx::query_as::<_, Event>(
+        "INSERT INTO events (name, description, location, starts_at, ends_at, invite_all, display_order)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING *"
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.location)
+    .bind(req.starts_at)
+    .bind(req.ends_at)
+    .bind(req.invite_all.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-// Look up invite by code for RSVP
-async fn rsvp_lookup(
+    Ok(Json(event))
+}
+
+// Update event (admin)
+async fn admin_update_event(
     State(state): State<AppState>,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    Path(code): Path<String>,
-) -> Result<Json<InviteRsvpResponse>, StatusCode> {
-    // Rate limit: 10 lookups per IP per 60 seconds
-    let ip = addr.ip().to_string();
-    if !state.rsvp_limiter.check(&ip, 10, 60).await {
-        tracing::warn!("RSVP rate limit exceeded for IP: {}", ip);
-        return Err(StatusCode::TOO_MANY_REQUESTS);
-    }
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateEventRequest>,
+) -> Result<Json<Event>, StatusCode> {
+    let event = sqlx::query_as::<_, Event>(
+        "UPDATE events
+         SET name = $1, description = $2, location = $3, starts_at = $4, ends_at = $5, invite_all = $6, display_order = $7
+         WHERE id = $8
+         RETURNING *"
+    )
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.location)
+    .bind(req.starts_at)
+    .bind(req.ends_at)
+    .bind(req.invite_all.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
+    .bind(id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    // Find invite by unique code
-    let invite = sqlx::query_as::<_, Invite>(
-        "SELECT * FROM invites WHERE unique_code = $1"
+    Ok(Json(event))
+}
+
+// Delete event with its invitations and responses (admin)
+async fn admin_delete_event(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM events WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// List the invites asked to an event (admin)
+async fn admin_list_event_invites(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Uuid>>, StatusCode> {
+    let invite_ids = sqlx::query_scalar::<_, Uuid>(
+        "SELECT invite_id FROM invite_events WHERE event_id = $1"
     )
-    .bind(&code)
-    .fetch_optional(&state.db)
+    .bind(id)
+    .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let invite = match invite {
-        Some(inv) => inv,
-        None => {
-            // Add a small delay on failed lookups to slow brute-force
-            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-            return Err(StatusCode::NOT_FOUND);
-        }
-    };
+    Ok(Json(invite_ids))
+}
+
+// Replace the invites asked to an event (admin)
+async fn admin_set_event_invites(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<SetEventInvitesRequest>,
+) -> Result<Json<Vec<Uuid>>, StatusCode> {
+    let mut tx = state.db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Get guests for this invite
-    let guests = sqlx::query_as::<_, Guest>(
-        "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
+    sqlx::query("DELETE FROM invite_events WHERE event_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    sqlx::query(
+        "INSERT INTO invite_events (invite_id, event_id)
+         SELECT UNNEST($1::uuid[]), $2
+         ON CONFLICT DO NOTHING"
     )
-    .bind(invite.id)
-    .fetch_all(&state.db)
+    .bind(&req.invite_ids)
+    .bind(id)
+    .execute(&mut *tx)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .map_err(|_| StatusCode::BAD_REQUEST)?;
 
-    if guests.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+    tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(req.invite_ids))
+}
+
+// ============ CUSTOM QUESTION ROUTES ============
+
+fn validate_question_request(req: &CreateRsvpQuestionRequest) -> Result<(), StatusCode> {
+    let scope = req.scope.as_deref().unwrap_or(QUESTION_SCOPE_GUEST);
+    let has_choices = req.choices.as_ref().is_some_and(|c| !c.is_empty());
+    let needs_choices = matches!(req.question_type.as_str(), "single_choice" | "multi_choice");
+
+    if req.prompt.trim().is_empty()
+        || !QUESTION_TYPES.contains(&req.question_type.as_str())
+        || ![QUESTION_SCOPE_GUEST, QUESTION_SCOPE_INVITE].contains(&scope)
+        || needs_choices != has_choices
+    {
+        return Err(StatusCode::BAD_REQUEST);
     }
 
-    // Get existing RSVPs for guests in this invite
-    let guest_ids: Vec<Uuid> = guests.iter().map(|g| g.id).collect();
-    let rsvps = sqlx::query_as::<_, Rsvp>(
-        "SELECT * FROM rsvps WHERE guest_id = ANY($1)"
+    Ok(())
+}
+
+// List all questions, including inactive ones (admin)
+async fn admin_list_questions(State(state): State<AppState>) -> Result<Json<Vec<RsvpQuestion>>, StatusCode> {
+    let questions = sqlx::query_as::<_, RsvpQuestion>(
+        "SELECT * FROM rsvp_questions ORDER BY display_order, created_at"
     )
-    .bind(&guest_ids)
     .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let already_responded = !rsvps.is_empty();
-
-    Ok(Json(InviteRsvpResponse {
-        invite: InviteWithGuests {
-            invite,
-            guests,
-        },
-        rsvps,
-        already_responded,
-    }))
+    Ok(Json(questions))
 }
 
-// Submit/update RSVP for an invite
-async fn rsvp_submit(
+// Create question (admin)
+async fn admin_create_question(
     State(state): State<AppState>,
-    Path(code): Path<String>,
-    Json(submission): Json<InviteRsvpSubmission>,
-) -> Result<Json<Vec<Rsvp>>, StatusCode> {
-    // Find invite by unique code
-    let invite = sqlx::query_as::<_, Invite>(
-        "SELECT * FROM invites WHERE unique_code = $1"
+    Json(req): Json<CreateRsvpQuestionRequest>,
+) -> Result<Json<RsvpQuestion>, StatusCode> {
+    validate_question_request(&req)?;
+
+    let question = sqlx::query_as::<_, RsvpQuestion>(
+        "INSERT INTO rsvp_questions (prompt, question_type, scope, required, choices, display_order, active)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING *"
     )
-    .bind(&code)
-    .fetch_optional(&state.db)
+    .bind(req.prompt.trim())
+    .bind(&req.question_type)
+    .bind(req.scope.as_deref().unwrap_or(QUESTION_SCOPE_GUEST))
+    .bind(req.required.unwrap_or(false))
+    .bind(req.choices.clone().unwrap_or_default())
+    .bind(req.display_order.unwrap_or(0))
+    .bind(req.active.unwrap_or(true))
+    .fetch_one(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let invite = match invite {
-        Some(inv) => inv,
-        None => return Err(StatusCode::NOT_FOUND),
-    };
+    Ok(Json(question))
+}
 
-    // Verify all guest_ids belong to this invite
-    let invite_guest_ids: Vec<Uuid> = sqlx::query_scalar(
-        "SELECT id FROM guests WHERE invite_id = $1 AND removed = false"
+// Update question (admin)
+async fn admin_update_question(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateRsvpQuestionRequest>,
+) -> Result<Json<RsvpQuestion>, StatusCode> {
+    validate_question_request(&req)?;
+
+    let question = sqlx::query_as::<_, RsvpQuestion>(
+        "UPDATE rsvp_questions
+         SET prompt = $1, question_type = $2, scope = $3, required = $4, choices = $5, display_order = $6, active = $7
+         WHERE id = $8
+         RETURNING *"
     )
-    .bind(invite.id)
-    .fetch_all(&state.db)
+    .bind(req.prompt.trim())
+    .bind(&req.question_type)
+    .bind(req.scope.as_deref().unwrap_or(QUESTION_SCOPE_GUEST))
+    .bind(req.required.unwrap_or(false))
+    .bind(req.choices.clone().unwrap_or_default())
+    .bind(req.display_order.unwrap_or(0))
+    .bind(req.active.unwrap_or(true))
+    .bind(id)
+    .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    for entry in &submission.guests {
-        if !invite_guest_ids.contains(&entry.guest_id) {
-            return Err(StatusCode::BAD_REQUEST);
-        }
-    }
+    Ok(Json(question))
+}
 
-    // Upsert RSVPs for each guest
-    let mut result_rsvps = Vec::new();
-    for entry in &submission.guests {
-        let rsvp = sqlx::query_as::<_, Rsvp>(
-            "INSERT INTO rsvps (guest_id, invite_id, attending, dietary_restrictions, song_requests, message)
-             VALUES ($1, $2, $3, $4, $5, $6)
-             ON CONFLICT (guest_id) DO UPDATE SET
-                attending = EXCLUDED.attending,
-                dietary_restrictions = EXCLUDED.dietary_restrictions,
-                song_requests = EXCLUDED.song_requests,
-                message = EXCLUDED.message,
-                updated_at = NOW()
-             RETURNING *"
-        )
-        .bind(entry.guest_id)
-        .bind(invite.id)
-        .bind(entry.attending)
-        .bind(&entry.dietary_restrictions)
-        .bind(&entry.song_requests)
-        .bind(&entry.message)
-        .fetch_one(&state.db)
+// Delete question and its answers (admin)
+async fn admin_delete_question(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM rsvp_questions WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        result_rsvps.push(rsvp);
-    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ MENU & CATERER ROUTES ============
+
+async fn load_menu(db: &PgPool) -> Result<Vec<CourseWithOptions>, sqlx::Error> {
+    let courses = sqlx::query_as::<_, MenuCourse>(
+        "SELECT * FROM menu_courses ORDER BY display_order, name"
+    )
+    .fetch_all(db)
+    .await?;
+
+    let options = sqlx::query_as::<_, MenuOption>(
+        "SELECT * FROM menu_options ORDER BY display_order, name"
+    )
+    .fetch_all(db)
+    .await?;
 
-    Ok(Json(result_rsvps))
+    Ok(courses
+        .into_iter()
+        .map(|course| CourseWithOptions {
+            options: options.iter().filter(|o| o.course_id == course.id).cloned().collect(),
+            course,
+        })
+        .collect())
 }
 
-// ============ ADMIN RSVP ROUTES ============
+// List the menu with options (admin)
+async fn admin_list_menu(State(state): State<AppState>) -> Result<Json<Vec<CourseWithOptions>>, StatusCode> {
+    let menu = load_menu(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-// Get RSVP statistics
-async fn admin_rsvp_stats(State(state): State<AppState>) -> Result<Json<RsvpStats>, StatusCode> {
-    // Total invited = all non-removed guests with invites
-    let total_invited: i64 = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT COUNT(*) FROM guests WHERE removed = false AND invite_id IS NOT NULL"
+    Ok(Json(menu))
+}
+
+// Create menu course (admin)
+async fn admin_create_menu_course(
+    State(state): State<AppState>,
+    Json(req): Json<CreateMenuCourseRequest>,
+) -> Result<Json<MenuCourse>, StatusCode> {
+    let course = sqlx::query_as::<_, MenuCourse>(
+        "INSERT INTO menu_courses (name, required, display_order) VALUES ($1, $2, $3) RETURNING *"
     )
+    .bind(&req.name)
+    .bind(req.required.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Total responded = guests with RSVPs
-    let total_responded: i64 = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT COUNT(*) FROM rsvps r
-         INNER JOIN guests g ON g.id = r.guest_id
-         WHERE g.removed = false"
+    Ok(Json(course))
+}
+
+// Update menu course (admin)
+async fn admin_update_menu_course(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateMenuCourseRequest>,
+) -> Result<Json<MenuCourse>, StatusCode> {
+    let course = sqlx::query_as::<_, MenuCourse>(
+        "UPDATE menu_courses SET name = $1, required = $2, display_order = $3 WHERE id = $4 RETURNING *"
     )
+    .bind(&req.name)
+    .bind(req.required.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
+    .bind(id)
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
+    .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    // Total attending
-    let total_attending: i64 = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT COUNT(*) FROM rsvps r
-         INNER JOIN guests g ON g.id = r.guest_id
-         WHERE g.removed = false AND r.attending = true"
+    Ok(Json(course))
+}
+
+// Delete menu course and its options (admin)
+async fn admin_delete_menu_course(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM menu_courses WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Create menu option (admin)
+async fn admin_create_menu_option(
+    State(state): State<AppState>,
+    Json(req): Json<CreateMenuOptionRequest>,
+) -> Result<Json<MenuOption>, StatusCode> {
+    let option = sqlx::query_as::<_, MenuOption>(
+        "INSERT INTO menu_options (course_id, name, description, is_kids_meal, display_order)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING *"
     )
+    .bind(req.course_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.is_kids_meal.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
+    .map_err(|_| StatusCode::BAD_REQUEST)?;
 
-    // Total declined
-    let total_declined: i64 = sqlx::query_scalar::<_, Option<i64>>(
-        "SELECT COUNT(*) FROM rsvps r
-         INNER JOIN guests g ON g.id = r.guest_id
-         WHERE g.removed = false AND r.attending = false"
+    Ok(Json(option))
+}
+
+// Update menu option (admin)
+async fn admin_update_menu_option(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateMenuOptionRequest>,
+) -> Result<Json<MenuOption>, StatusCode> {
+    let option = sqlx::query_as::<_, MenuOption>(
+        "UPDATE menu_options
+         SET course_id = $1, name = $2, description = $3, is_kids_meal = $4, display_order = $5
+         WHERE id = $6
+         RETURNING *"
     )
+    .bind(req.course_id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(req.is_kids_meal.unwrap_or(false))
+    .bind(req.display_order.unwrap_or(0))
+    .bind(id)
     .fetch_one(&state.db)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .unwrap_or(0);
-
-    let total_pending = total_invited - total_responded;
+    .map_err(|_| StatusCode::NOT_FOUND)?;
 
-    Ok(Json(RsvpStats {
-        total_invited,
-        total_responded,
-        total_attending,
-        total_declined,
-        total_pending,
-    }))
+    Ok(Json(option))
 }
 
-// List all RSVPs grouped by invite
-async fn admin_list_rsvps(State(state): State<AppState>) -> Result<Json<Vec<AdminRsvpEntry>>, StatusCode> {
-    // Get all invites with guests
-    let invites = sqlx::query_as::<_, Invite>("SELECT * FROM invites ORDER BY created_at DESC")
-        .fetch_all(&state.db)
+// Delete menu option (admin)
+async fn admin_delete_menu_option(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, StatusCode> {
+    sqlx::query("DELETE FROM menu_options WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let mut entries = Vec::new();
+    Ok(StatusCode::NO_CONTENT)
+}
 
-    for invite in invites {
-        let guests = sqlx::query_as::<_, Guest>(
-            "SELECT * FROM guests WHERE invite_id = $1 AND removed = false ORDER BY name"
-        )
-        .bind(invite.id)
-        .fetch_all(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+async fn build_caterer_report(db: &PgPool) -> Result<CatererReport, sqlx::Error> {
+    let attending_guests: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND r.attending = true"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
 
-        if guests.is_empty() {
-            continue;
-        }
+    // Attending guests who have not picked an option for every required
+    // course; optional courses may be skipped
+    let missing_selections: i64 = sqlx::query_scalar::<_, Option<i64>>(
+        "SELECT COUNT(*) FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND r.attending = true
+           AND EXISTS (
+               SELECT 1 FROM menu_courses c
+               WHERE c.required = true
+                 AND NOT EXISTS (
+                     SELECT 1 FROM rsvp_meal_selections s
+                     WHERE s.guest_id = g.id AND s.course_id = c.id
+                 )
+           )"
+    )
+    .fetch_one(db)
+    .await?
+    .unwrap_or(0);
 
-        let guest_ids: Vec<Uuid> = guests.iter().map(|g| g.id).collect();
-        let rsvps = sqlx::query_as::<_, Rsvp>(
-            "SELECT * FROM rsvps WHERE guest_id = ANY($1)"
-        )
-        .bind(&guest_ids)
-        .fetch_all(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let totals = sqlx::query_as::<_, MealOptionTotal>(
+        "SELECT c.id AS course_id, c.name AS course_name, o.id AS option_id, o.name AS option_name,
+                o.is_kids_meal, COUNT(g.id) AS count,
+                COUNT(g.id) FILTER (WHERE g.age_group = 'adult') AS adult_count,
+                COUNT(g.id) FILTER (WHERE g.age_group = 'child') AS child_count,
+                COUNT(g.id) FILTER (WHERE g.age_group = 'infant') AS infant_count
+         FROM menu_options o
+         INNER JOIN menu_courses c ON c.id = o.course_id
+         LEFT JOIN rsvp_meal_selections s ON s.option_id = o.id
+         LEFT JOIN rsvps r ON r.guest_id = s.guest_id AND r.attending = true
+         LEFT JOIN guests g ON g.id = r.guest_id AND g.removed = false
+         GROUP BY c.id, c.name, c.display_order, o.id, o.name, o.is_kids_meal, o.display_order
+         ORDER BY c.display_order, c.name, o.display_order, o.name"
+    )
+    .fetch_all(db)
+    .await?;
 
-        // Build RsvpWithGuest entries
-        let rsvps_with_guests: Vec<RsvpWithGuest> = rsvps.iter().map(|rsvp| {
-            let guest = guests.iter().find(|g| g.id == rsvp.guest_id);
-            RsvpWithGuest {
-                rsvp: rsvp.clone(),
-                guest_name: guest.map(|g| g.name.clone()).unwrap_or_default(),
-                guest_email: guest.map(|g| g.email.clone()).unwrap_or_default(),
-            }
-        }).collect();
+    let guests = sqlx::query_as::<_, GuestMealRow>(
+        "SELECT g.id AS guest_id, g.name AS guest_name, g.age_group, i.unique_code,
+                c.name AS course_name, o.name AS option_name, o.is_kids_meal,
+                r.dietary_restrictions
+         FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         LEFT JOIN invites i ON i.id = g.invite_id
+         LEFT JOIN rsvp_meal_selections s ON s.guest_id = g.id
+         LEFT JOIN menu_courses c ON c.id = s.course_id
+         LEFT JOIN menu_options o ON o.id = s.option_id
+         WHERE g.removed = false AND r.attending = true
+         ORDER BY g.name, c.display_order, c.name"
+    )
+    .fetch_all(db)
+    .await?;
+
+    let attending_by_age_group = sqlx::query_as::<_, AgeGroupStats>(
+        "SELECT g.age_group,
+                COUNT(*) AS invited,
+                COUNT(*) AS attending,
+                0::bigint AS declined,
+                0::bigint AS pending
+         FROM rsvps r
+         INNER JOIN guests g ON g.id = r.guest_id
+         WHERE g.removed = false AND r.attending = true
+         GROUP BY g.age_group
+         ORDER BY g.age_group"
+    )
+    .fetch_all(db)
+    .await?;
 
-        // Determine status
-        let status = if rsvps.is_empty() {
-            "pending".to_string()
-        } else if rsvps.len() < guests.len() {
-            "partial".to_string()
-        } else if rsvps.iter().all(|r| r.attending) {
-            "attending".to_string()
-        } else if rsvps.iter().all(|r| !r.attending) {
-            "declined".to_string()
-        } else {
-            "partial".to_string()
-        };
+    Ok(CatererReport {
+        attending_guests,
+        attending_by_age_group,
+        missing_selections,
+        totals,
+        guests,
+    })
+}
 
-        entries.push(AdminRsvpEntry {
-            invite: InviteWithGuests {
-                invite: invite.clone(),
-                guests,
-            },
-            rsvps: rsvps_with_guests,
-            status,
-        });
-    }
+// Get caterer report with totals per option and per-guest choices
+async fn admin_caterer_report(State(state): State<AppState>) -> Result<Json<CatererReport>, StatusCode> {
+    let report = build_caterer_report(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to build caterer report: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    Ok(Json(entries))
+    Ok(Json(report))
 }
 
-// Export RSVPs as CSV
-async fn admin_export_rsvps(State(state): State<AppState>) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], String), StatusCode> {
-    #[derive(sqlx::FromRow)]
-    struct ExportRow {
-        name: String,
-        email: String,
-        attending: bool,
-        dietary_restrictions: Option<String>,
-        song_requests: Option<String>,
-        message: Option<String>,
-        submitted_at: Option<time::OffsetDateTime>,
-    }
+// Export caterer report as CSV (per-guest listing, or totals with ?view=totals)
+async fn admin_export_caterer_report(
+    State(state): State<AppState>,
+    Query(query): Query<CatererExportQuery>,
+) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], String), StatusCode> {
+    let report = build_caterer_report(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to build caterer report: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    let rows = sqlx::query_as::<_, ExportRow>(
-        "SELECT g.name, g.email, r.attending, r.dietary_restrictions, r.song_requests, r.message, r.submitted_at
-         FROM rsvps r
-         INNER JOIN guests g ON g.id = r.guest_id
-         WHERE g.removed = false
-         ORDER BY g.name"
-    )
-    .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (csv, filename) = match query.view {
+        CatererExportView::Totals => {
+            let mut csv = String::from("Course,Option,Kids Meal,Count,Adults,Children,Infants\n");
+            for total in &report.totals {
+                csv.push_str(&format!(
+                    "\"{}\",\"{}\",{},{},{},{},{}\n",
+                    total.course_name.replace('"', "\"\""),
+                    total.option_name.replace('"', "\"\""),
+                    if total.is_kids_meal { "Yes" } else { "No" },
+                    total.count,
+                    total.adult_count,
+                    total.child_count,
+                    total.infant_count,
+                ));
+            }
+            (csv, "attachment; filename=\"meal-totals.csv\"")
+        }
+        CatererExportView::Guests => {
+            let mut csv = String::from("Name,Age Group,Invite Code,Course,Selection,Kids Meal,Dietary Restrictions\n");
+            for row in &report.guests {
+                csv.push_str(&format!(
+                    "\"{}\",{},\"{}\",\"{}\",\"{}\",{},\"{}\"\n",
+                    row.guest_name.replace('"', "\"\""),
+                    row.age_group,
+                    row.unique_code.as_deref().unwrap_or(""),
+                    row.course_name.as_deref().unwrap_or("").replace('"', "\"\""),
+                    row.option_name.as_deref().unwrap_or("Not chosen").replace('"', "\"\""),
+                    if row.is_kids_meal.unwrap_or(false) { "Yes" } else { "No" },
+                    row.dietary_restrictions.as_deref().unwrap_or("").replace('"', "\"\""),
+                ));
+            }
+            (csv, "attachment; filename=\"meal-selections.csv\"")
+        }
+    };
 
-    let mut csv = String::from("Name,Email,Attending,Dietary Restrictions,Song Requests,Message,Submitted At\n");
-    for row in &rows {
-        let submitted_str = row.submitted_at.map(|t| t.to_string()).unwrap_or_default();
-        csv.push_str(&format!(
-            "\"{}\",\"{}\",{},\"{}\",\"{}\",\"{}\",\"{}\"\n",
-            row.name.replace('"', "\"\""),
-            row.email.replace('"', "\"\""),
-            if row.attending { "Yes" } else { "No" },
-            row.dietary_restrictions.as_deref().unwrap_or("").replace('"', "\"\""),
-            row.song_requests.as_deref().unwrap_or("").replace('"', "\"\""),
-            row.message.as_deref().unwrap_or("").replace('"', "\"\""),
-            submitted_str,
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("text/csv")),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static(filename)),
+        ],
+        csv,
+    ))
+}
+
+// Export caterer report as PDF
+async fn admin_caterer_report_pdf(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, [(axum::http::HeaderName, axum::http::HeaderValue); 2], Vec<u8>), StatusCode> {
+    let report = build_caterer_report(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to build caterer report: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut lines = vec![
+        format!("Attending guests: {}", report.attending_guests),
+        format!(
+            "By age group: {}",
+            report.attending_by_age_group.iter()
+                .map(|g| format!("{} {}", g.attending, g.age_group))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        format!("Guests missing a selection: {}", report.missing_selections),
+        String::new(),
+        "TOTALS".to_string(),
+    ];
+    let mut current_course = None;
+    for total in &report.totals {
+        if current_course != Some(total.course_id) {
+            current_course = Some(total.course_id);
+            lines.push(format!("{}:", total.course_name));
+        }
+        let kids = if total.is_kids_meal { " (kids)" } else { "" };
+        lines.push(format!(
+            "    {}{} - {} ({} adult, {} child, {} infant)",
+            total.option_name, kids, total.count, total.adult_count, total.child_count, total.infant_count
         ));
     }
 
+    lines.push(String::new());
+    lines.push("GUESTS".to_string());
+    let mut current_guest = None;
+    for row in &report.guests {
+        if current_guest != Some(row.guest_id) {
+            current_guest = Some(row.guest_id);
+            let dietary = row.dietary_restrictions.as_deref()
+                .filter(|d| !d.trim().is_empty())
+                .map(|d| format!(" - Dietary: {}", d))
+                .unwrap_or_default();
+            let age = if row.age_group == AGE_ADULT { String::new() } else { format!(" ({})", row.age_group) };
+            lines.push(format!("{}{}{}", row.guest_name, age, dietary));
+        }
+        if let (Some(course), Some(option)) = (&row.course_name, &row.option_name) {
+            lines.push(format!("    {}: {}", course, option));
+        } else {
+            lines.push("    No meal selected".to_string());
+        }
+    }
+
+    let pdf = crate::pdf::text_document("Caterer Report - Sam & Jonah's Wedding", &lines);
+
     Ok((
         StatusCode::OK,
         [
-            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("text/csv")),
-            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static("attachment; filename=\"rsvps.csv\"")),
+            (header::CONTENT_TYPE, axum::http::HeaderValue::from_static("application/pdf")),
+            (header::CONTENT_DISPOSITION, axum::http::HeaderValue::from_static("attachment; filename=\"caterer-report.pdf\"")),
         ],
-        csv,
+        pdf,
     ))
 }
 
 // ============ ADMIN INVITATION ROUTES ============
 
+/// Check a send of up to `planned` emails against this month's quota.
+/// Gives a low-quota warning to pass along, or the response refusing the send.
+async fn quota_check(
+    db: &PgPool,
+    planned: usize,
+) -> Result<Result<Option<String>, SendInvitationResponse>, StatusCode> {
+    let quota = email_quota(db).await?;
+    if let Some(refusal) = provider::refusal(&quota, planned) {
+        tracing::warn!("Refused send: {}", refusal);
+        return Ok(Err(SendInvitationResponse {
+            success: false,
+            sent_count: 0,
+            errors: vec![refusal],
+            suppressed: Vec::new(),
+            quota_warning: None,
+        }));
+    }
+    Ok(Ok(provider::warning(&quota, planned)))
+}
+
 // Send invitation emails to selected invites
 async fn admin_send_invitations(
     State(state): State<AppState>,
     Json(req): Json<SendInvitationRequest>,
 ) -> Result<Json<SendInvitationResponse>, StatusCode> {
-    let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
-    let resend_api_key = std::env::var("RESEND_API_KEY")
-        .map_err(|_| {
-            tracing::error!("RESEND_API_KEY environment variable not set");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let from_email = std::env::var("FROM_EMAIL")
-        .unwrap_or_else(|_| "contact@samandjonah.com".to_string());
+    let email_service = sending_email_service(&state.db)?;
+
+    // Guests with a usable address on the invites being sent, as
+    // is_valid_email checks it
+    let planned = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM guests
+         WHERE invite_id = ANY($1) AND removed = false AND split_part(email, '@', 2) LIKE '%.%'"
+    )
+    .bind(&req.invite_ids)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let quota_warning = match quota_check(&state.db, planned as usize).await? {
+        Ok(warning) => warning,
+        Err(refused) => return Ok(Json(refused)),
+    };
+    let campaign = email_service.invitation_campaign().await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     let mut sent_count = 0;
+    let mut suppressed = Vec::new();
     let mut errors = Vec::new();
 
     for invite_id in &req.invite_ids {
@@ -1699,82 +5384,43 @@ async fn admin_send_invitations(
             continue;
         }
 
-        // Get valid email addresses
-        let recipient_emails: Vec<String> = guests.iter()
-            .filter(|g| g.email.contains('@') && g.email.split('@').nth(1).map_or(false, |d| d.contains('.')))
-            .map(|g| g.email.clone())
-            .collect();
-
-        if recipient_emails.is_empty() {
-            errors.push(format!("No valid emails for invite {}", invite.unique_code));
-            continue;
-        }
-
-        let guest_names: Vec<String> = guests.iter().map(|g| g.name.clone()).collect();
-        let names_display = if guest_names.len() == 1 {
-            guest_names[0].clone()
-        } else {
-            format!("{} & {}", guest_names[0], guest_names[1])
-        };
-
-        let rsvp_link = format!("{}/rsvp?code={}", frontend_url, invite.unique_code);
-
-        // Build invitation email HTML (placeholder — will be replaced with image/PDF later)
-        let html = crate::email::templates::invitation_email_html(
-            &names_display,
-            &rsvp_link,
-            &frontend_url,
-        );
-
-        // Send via Resend
-        let email_payload = serde_json::json!({
-            "from": format!("Sam & Jonah <{}>", from_email),
-            "to": recipient_emails,
-            "subject": "You're Invited! Sam & Jonah's Wedding",
-            "html": html,
-            "reply_to": [from_email],
-        });
-
-        let client = reqwest::Client::new();
-        let response = client
-            .post("https://api.resend.com/emails")
-            .header("Authorization", format!("Bearer {}", resend_api_key))
-            .header("Content-Type", "application/json")
-            .json(&email_payload)
-            .send()
-            .await;
-
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
+        let names_display = guests.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(" & ");
+        let invite = InviteWithGuests { invite, guests };
+        match email_service.send_invitation(&campaign, &invite).await {
+            Ok(outcome) => {
+                if outcome.email_send_id.is_some() {
                     sent_count += 1;
-                    // Mark invite as sent
-                    let _ = sqlx::query(
-                        "UPDATE invites SET invite_sent_at = NOW() WHERE id = $1"
-                    )
-                    .bind(invite.id)
-                    .execute(&state.db)
-                    .await;
-                    tracing::info!("✉️ Sent invitation to {} ({})", names_display, invite.unique_code);
-                } else {
-                    let status = resp.status();
-                    let body = resp.text().await.unwrap_or_default();
-                    errors.push(format!("Resend error for {}: {} - {}", invite.unique_code, status, body));
+                    tracing::info!("✉️ Sent invitation to {} ({})", names_display, invite.invite.unique_code);
                 }
+                suppressed.extend(outcome.suppressed);
             }
             Err(e) => {
-                errors.push(format!("Failed to send to {}: {}", invite.unique_code, e));
+                errors.push(format!("{}: {}", invite.invite.unique_code, e));
             }
         }
     }
 
+    email_service.update_campaign_totals(campaign.id, sent_count).await.map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     Ok(Json(SendInvitationResponse {
         success: errors.is_empty(),
         sent_count,
         errors,
+        suppressed,
+        quota_warning,
     }))
 }
 
+async fn admin_test_send_invitation(
+    State(state): State<AppState>,
+    Json(req): Json<TestSendRequest>,
+) -> Result<Json<TestSendResponse>, StatusCode> {
+    send_test_email(&state.db, INVITATION_TEMPLATE, None, req).await
+}
+
 // Get invitation send status for all invites
 async fn admin_invitation_status(
     State(state): State<AppState>,
@@ -1809,155 +5455,142 @@ async fn admin_invitation_status(
     Ok(Json(result))
 }
 
-// ============ ONE-MONTH REMINDER ROUTES ============
+// ============ REMINDER ROUTES ============
 
-async fn attending_invites(db: &PgPool) -> Result<Vec<InviteWithGuests>, StatusCode> {
-    let invites = sqlx::query_as::<_, Invite>(
-        "SELECT DISTINCT i.*
-         FROM invites i
-         INNER JOIN guests g ON g.invite_id = i.id
-         INNER JOIN rsvps r ON r.guest_id = g.id
-         WHERE g.removed = false AND r.attending = true
-         ORDER BY i.created_at"
+fn find_reminder(key: &str) -> Result<&'static Reminder, StatusCode> {
+    reminders::find(key).ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn reminder_audience(db: &PgPool, reminder: &Reminder) -> Result<Vec<InviteWithGuests>, StatusCode> {
+    reminder.audience(db).await.map_err(|e| {
+        tracing::error!("Failed to fetch {} reminder recipients: {}", reminder.key, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+async fn admin_list_reminders() -> Json<&'static [Reminder]> {
+    Json(reminders::REMINDERS)
+}
+
+async fn admin_reminder_status(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+) -> Result<Json<ReminderStatus>, StatusCode> {
+    let reminder = find_reminder(&key)?;
+    let invites = reminder_audience(&state.db, reminder).await?;
+
+    let sent = sqlx::query_as::<_, (Uuid, Option<time::OffsetDateTime>)>(
+        "SELECT es.invite_id, MAX(es.sent_at)
+         FROM email_sends es
+         INNER JOIN email_campaigns ec ON ec.id = es.campaign_id
+         WHERE ec.reminder_type = $1 AND es.invite_id IS NOT NULL
+         GROUP BY es.invite_id"
     )
-    .fetch_all(db)
+    .bind(reminder.template_type)
+    .fetch_all(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Failed to fetch one-month reminder recipients: {}", e);
+        tracing::error!("Failed to fetch {} reminder status: {}", reminder.key, e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    let sent: HashMap<Uuid, Option<time::OffsetDateTime>> = sent.into_iter().collect();
 
-    let mut result = Vec::with_capacity(invites.len());
-    for invite in invites {
-        // Only confirmed attendees are included. Declined guests on the same
-        // invite must not receive or be named in this reminder.
-        let guests = sqlx::query_as::<_, Guest>(
-            "SELECT g.*
-             FROM guests g
-             INNER JOIN rsvps r ON r.guest_id = g.id
-             WHERE g.invite_id = $1 AND g.removed = false AND r.attending = true
-             ORDER BY g.name"
-        )
-        .bind(invite.id)
-        .fetch_all(db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch attendees for invite {}: {}", invite.id, e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let recipients = invites.into_iter()
+        .map(|invite| ReminderRecipient {
+            sent_at: sent.get(&invite.invite.id).copied().flatten(),
+            invite,
+        })
+        .collect();
 
-        if !guests.is_empty() {
-            result.push(InviteWithGuests { invite, guests });
-        }
-    }
+    let subject = reminder.current_subject(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(result)
+    Ok(Json(ReminderStatus { reminder, subject, recipients }))
 }
 
-async fn admin_one_month_reminder_status(
+/// Preview a reminder for an invite in its audience (`?invite=` id or code),
+/// or for the first one.
+async fn admin_reminder_preview(
     State(state): State<AppState>,
-) -> Result<Json<OneMonthReminderStatus>, StatusCode> {
-    let invites = attending_invites(&state.db).await?;
-    let mut recipients = Vec::with_capacity(invites.len());
+    Path(key): Path<String>,
+    Query(query): Query<CampaignPreviewQuery>,
+) -> Result<Html<String>, StatusCode> {
+    let reminder = find_reminder(&key)?;
+    let audience = reminder_audience(&state.db, reminder).await?;
+    let invite = match query.invite.as_deref().map(str::trim).filter(|i| !i.is_empty()) {
+        Some(wanted) => Some(
+            audience.into_iter()
+                .find(|i| i.invite.unique_code == wanted || i.invite.id.to_string() == wanted)
+                .ok_or(StatusCode::NOT_FOUND)?,
+        ),
+        None => audience.into_iter().next(),
+    };
 
-    for invite in invites {
-        let sent_at = sqlx::query_scalar::<_, Option<time::OffsetDateTime>>(
-            "SELECT MAX(es.sent_at)
-             FROM email_sends es
-             INNER JOIN email_campaigns ec ON ec.id = es.campaign_id
-             WHERE es.invite_id = $1 AND ec.template_type = $2"
-        )
-        .bind(invite.invite.id)
-        .bind(ONE_MONTH_REMINDER_TEMPLATE)
-        .fetch_one(&state.db)
+    let Some(invite) = invite else {
+        return Ok(Html(format!(
+            "<html><body><h1>No recipients yet</h1><p>{} has no recipients yet. It goes to: {}.</p></body></html>",
+            escape_html(reminder.name),
+            escape_html(reminder.description),
+        )));
+    };
+
+    let email_service = configured_email_service(&state.db, String::new());
+    let subject = reminder.current_subject(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let email = email_service
+        .render_template(reminder.template_type, Some(&subject), &invite)
         .await
         .map_err(|e| {
-            tracing::error!("Failed to fetch one-month reminder status: {}", e);
+            tracing::error!("{}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-        recipients.push(OneMonthReminderRecipient { invite, sent_at });
-    }
-
-    Ok(Json(OneMonthReminderStatus {
-        subject: ONE_MONTH_REMINDER_SUBJECT.to_string(),
-        recipients,
-    }))
+    Ok(Html(email.html))
 }
 
-async fn admin_one_month_reminder_preview(
+async fn admin_test_send_reminder(
     State(state): State<AppState>,
-) -> Result<Html<String>, StatusCode> {
-    let sample_invite = attending_invites(&state.db).await?.into_iter().next();
-    let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
-
-    let html = if let Some(invite) = sample_invite {
-        let email_service = EmailService::new(
-            state.db.clone(),
-            frontend_url,
-            String::new(),
-            String::new(),
-            String::new(),
-            String::new(),
-        );
-        email_service.render_one_month_reminder(&invite)
-    } else {
-        crate::email::templates::one_month_reminder_html(
-            &["Alex".to_string()],
-            &frontend_url,
-        )
-    };
-
-    Ok(Html(html))
-}
+    Path(key): Path<String>,
+    Json(req): Json<TestSendRequest>,
+) -> Result<Json<TestSendResponse>, StatusCode> {
+    let reminder = find_reminder(&key)?;
+    // Use the reminder campaign's subject without creating the campaign
+    let subject = reminder.current_subject(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-async fn get_or_create_one_month_campaign(db: &PgPool) -> Result<EmailCampaign, String> {
-    sqlx::query_as::<_, EmailCampaign>(
-        "INSERT INTO email_campaigns (name, subject, template_type)
-         VALUES ($1, $2, $3)
-         ON CONFLICT (template_type) WHERE template_type = 'one_month_reminder'
-         DO UPDATE SET template_type = EXCLUDED.template_type
-         RETURNING *"
-    )
-    .bind(ONE_MONTH_REMINDER_NAME)
-    .bind(ONE_MONTH_REMINDER_SUBJECT)
-    .bind(ONE_MONTH_REMINDER_TEMPLATE)
-    .fetch_one(db)
-    .await
-    .map_err(|e| format!("Failed to create one-month reminder campaign: {}", e))
+    send_test_email(&state.db, reminder.template_type, Some(&subject), req).await
 }
 
-async fn admin_send_one_month_reminders(
+async fn admin_send_reminders(
     State(state): State<AppState>,
+    Path(key): Path<String>,
     Json(req): Json<SendInvitationRequest>,
 ) -> Result<Json<SendInvitationResponse>, StatusCode> {
-    let frontend_url = std::env::var("FRONTEND_URL")
-        .unwrap_or_else(|_| "https://samandjonah.com".to_string());
-    let resend_api_key = std::env::var("RESEND_API_KEY")
-        .map_err(|_| {
-            tracing::error!("RESEND_API_KEY environment variable not set");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let from_email = std::env::var("FROM_EMAIL")
-        .unwrap_or_else(|_| "contact@samandjonah.com".to_string());
+    let reminder = find_reminder(&key)?;
+    let email_service = sending_email_service(&state.db)?;
 
-    let campaign = get_or_create_one_month_campaign(&state.db)
+    let campaign = reminder.campaign(&state.db)
         .await
         .map_err(|e| {
-            tracing::error!("{}", e);
+            tracing::error!("Failed to create {} reminder campaign: {}", reminder.key, e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
-    let email_service = EmailService::new(
-        state.db.clone(),
-        frontend_url,
-        resend_api_key,
-        from_email,
-        String::new(),
-        String::new(),
-    );
+    let audience = reminder_audience(&state.db, reminder).await?;
+    let planned = audience.iter()
+        .filter(|i| req.invite_ids.contains(&i.invite.id))
+        .flat_map(|i| &i.guests)
+        .filter(|g| is_valid_email(&g.email))
+        .count();
+    let quota_warning = match quota_check(&state.db, planned).await? {
+        Ok(warning) => warning,
+        Err(refused) => return Ok(Json(refused)),
+    };
 
     let mut sent_count = 0;
+    let mut suppressed = Vec::new();
     let mut errors = Vec::new();
 
     for invite_id in &req.invite_ids {
@@ -1982,43 +5615,26 @@ async fn admin_send_one_month_reminders(
             continue;
         }
 
-        let invite = sqlx::query_as::<_, Invite>("SELECT * FROM invites WHERE id = $1")
-            .bind(invite_id)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let Some(invite) = invite else {
-            errors.push(format!("Invite {} not found", invite_id));
+        // Only the guests the reminder is addressed to are included, so e.g.
+        // declined guests on an invite are neither emailed nor named.
+        let Some(recipient) = audience.iter().find(|i| i.invite.id == *invite_id) else {
+            errors.push(format!("Invite {} has no recipients for this reminder", invite_id));
             continue;
         };
 
-        let guests = sqlx::query_as::<_, Guest>(
-            "SELECT g.*
-             FROM guests g
-             INNER JOIN rsvps r ON r.guest_id = g.id
-             WHERE g.invite_id = $1 AND g.removed = false AND r.attending = true
-             ORDER BY g.name"
-        )
-        .bind(invite.id)
-        .fetch_all(&state.db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        if guests.is_empty() {
-            errors.push(format!("Invite {} has no confirmed attendees", invite.unique_code));
-            continue;
-        }
-
-        let unique_code = invite.unique_code.clone();
-        let recipient = InviteWithGuests { invite, guests };
+        let unique_code = &recipient.invite.unique_code;
         match email_service
-            .send_one_month_reminder(campaign.id, &recipient, &campaign.subject)
+            .send_reminder(reminder, campaign.id, recipient, &campaign.subject)
             .await
         {
-            Ok(_) => sent_count += 1,
+            Ok(outcome) => {
+                if outcome.email_send_id.is_some() {
+                    sent_count += 1;
+                }
+                suppressed.extend(outcome.suppressed);
+            }
             Err(e) => {
-                tracing::error!("Failed to send one-month reminder to {}: {}", unique_code, e);
+                tracing::error!("Failed to send {} reminder to {}: {}", reminder.key, unique_code, e);
                 errors.push(format!("{}: {}", unique_code, e));
             }
         }
@@ -2037,7 +5653,7 @@ async fn admin_send_one_month_reminders(
     .execute(&state.db)
     .await
     .map_err(|e| {
-        tracing::error!("Failed to update one-month reminder campaign: {}", e);
+        tracing::error!("Failed to update {} reminder campaign: {}", reminder.key, e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
@@ -2045,6 +5661,8 @@ async fn admin_send_one_month_reminders(
         success: errors.is_empty(),
         sent_count,
         errors,
+        suppressed,
+        quota_warning,
     }))
 }
 
@@ -2087,3 +5705,226 @@ async fn admin_registry_stats(State(state): State<AppState>) -> Result<Json<Regi
         item_count,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        delivery_status_for_event, names_match, permanent_bounce_address, normalize_answer, normalize_answers, normalize_postal_code,
+        rsvp_changes,
+        validate_meal_selections, verify_webhook_signature,
+    };
+    use crate::models::{
+        CourseWithOptions, GuestRsvpEntry, MealSelection, MenuCourse, MenuOption, QuestionAnswer, Rsvp,
+        RsvpQuestion, DELIVERY_STATUSES,
+    };
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn entry(attending: bool, dietary: Option<&str>) -> GuestRsvpEntry {
+        GuestRsvpEntry {
+            guest_id: Uuid::nil(),
+            attending,
+            dietary_restrictions: dietary.map(str::to_string),
+            dietary_tags: None,
+            song_requests: None,
+            message: None,
+            meal_selections: None,
+            answers: None,
+            events: None,
+            publish_message: None,
+        }
+    }
+
+    #[test]
+    fn rsvp_changes_reports_flipped_attendance_and_ignores_blank_text() {
+        let previous = Rsvp {
+            id: Uuid::nil(),
+            guest_id: Uuid::nil(),
+            invite_id: None,
+            attending: true,
+            dietary_restrictions: Some("  ".to_string()),
+            dietary_tags: Vec::new(),
+            dietary_review_status: None,
+            song_requests: None,
+            message: None,
+            source: "guest_link".to_string(),
+            entered_by: None,
+            submitted_at: None,
+            updated_at: None,
+        };
+
+        let changes = rsvp_changes(Some(&previous), &entry(false, None));
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "attending");
+        assert_eq!(changes[0].from, Some(true.into()));
+        assert_eq!(changes[0].to, Some(false.into()));
+    }
+
+    #[test]
+    fn first_rsvp_reports_answered_fields() {
+        let changes = rsvp_changes(None, &entry(true, Some("vegetarian")));
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+
+        assert_eq!(fields, ["attending", "dietary_restrictions"]);
+        assert!(changes.iter().all(|c| c.from.is_none()));
+    }
+
+    #[test]
+    fn meal_selections_must_match_menu_and_cover_required_courses() {
+        let course = MenuCourse {
+            id: Uuid::new_v4(),
+            name: "Entree".to_string(),
+            required: true,
+            display_order: 0,
+            created_at: None,
+        };
+        let option = MenuOption {
+            id: Uuid::new_v4(),
+            course_id: course.id,
+            name: "Salmon".to_string(),
+            description: None,
+            is_kids_meal: false,
+            display_order: 0,
+            created_at: None,
+        };
+        let menu = vec![CourseWithOptions { course: course.clone(), options: vec![option.clone()] }];
+
+        let mut guest = entry(true, None);
+        guest.meal_selections = Some(vec![]);
+        assert!(validate_meal_selections(&menu, &guest, &[course.id]).is_err());
+
+        guest.meal_selections = Some(vec![MealSelection { course_id: course.id, option_id: Uuid::new_v4() }]);
+        assert!(validate_meal_selections(&menu, &guest, &[]).is_err());
+
+        guest.meal_selections = Some(vec![MealSelection { course_id: course.id, option_id: option.id }]);
+        assert!(validate_meal_selections(&menu, &guest, &[]).is_ok());
+
+        // Declined guests are not checked; omitted selections fall back to
+        // the stored ones
+        assert!(validate_meal_selections(&menu, &entry(false, None), &[]).is_ok());
+        assert!(validate_meal_selections(&menu, &entry(true, None), &[]).is_err());
+        assert!(validate_meal_selections(&menu, &entry(true, None), &[course.id]).is_ok());
+    }
+
+    #[test]
+    fn names_match_tolerates_small_typos_but_not_other_people() {
+        assert!(names_match("jonathan o'brien", "Jonathan OBrien"));
+        assert!(names_match("Margaret Smtih", "Margaret Smith"));
+        assert!(!names_match("Mark Smith", "Margaret Smith"));
+        assert!(!names_match("", "Margaret Smith"));
+        assert_eq!(normalize_postal_code(" t2g 0a1 "), "T2G0A1");
+    }
+
+    #[test]
+    fn webhook_signature_matches_svix_scheme() {
+        // base64("test-secret")
+        let secret = "whsec_dGVzdC1zZWNyZXQ=";
+        let body = r#"{"type":"email.opened"}"#;
+        let signature = {
+            use base64::Engine;
+            use hmac::{Hmac, Mac};
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"test-secret").unwrap();
+            mac.update(format!("msg_1.1700000000.{}", body).as_bytes());
+            base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+        };
+
+        let header = format!("v1,bogus v1,{}", signature);
+        let now = time::OffsetDateTime::from_unix_timestamp(1_700_000_120).unwrap();
+        assert!(verify_webhook_signature(secret, "msg_1", "1700000000", body, &header, now));
+        assert!(!verify_webhook_signature(secret, "msg_2", "1700000000", body, &header, now));
+        assert!(!verify_webhook_signature(secret, "msg_1", "1700000000", "{}", &header, now));
+
+        // Replays outside the five minute window are refused
+        let later = time::OffsetDateTime::from_unix_timestamp(1_700_000_301).unwrap();
+        assert!(!verify_webhook_signature(secret, "msg_1", "1700000000", body, &header, later));
+        assert!(!verify_webhook_signature(secret, "msg_1", "", body, &header, now));
+    }
+
+    #[test]
+    fn delivery_events_only_move_status_forward() {
+        let rank = |event: &str| {
+            let status = delivery_status_for_event(event).unwrap();
+            DELIVERY_STATUSES.iter().position(|s| *s == status).unwrap()
+        };
+
+        assert!(rank("email.sent") < rank("email.delivery_delayed"));
+        assert!(rank("email.delivery_delayed") < rank("email.delivered"));
+        assert!(rank("email.delivered") < rank("email.bounced"));
+        assert!(rank("email.delivered") < rank("email.complained"));
+        assert_eq!(delivery_status_for_event("email.opened"), None);
+    }
+
+    #[test]
+    fn normalize_answer_checks_type_and_choices() {
+        let question = |question_type: &str| RsvpQuestion {
+            id: Uuid::nil(),
+            prompt: "Need a shuttle?".to_string(),
+            question_type: question_type.to_string(),
+            scope: "guest".to_string(),
+            required: false,
+            choices: vec!["Hotel".to_string(), "Downtown".to_string()],
+            display_order: 0,
+            active: true,
+            created_at: None,
+        };
+
+        assert_eq!(normalize_answer(&question("yes_no"), &json!(true)).unwrap(), json!(true));
+        assert!(normalize_answer(&question("yes_no"), &json!("yes")).is_err());
+        assert_eq!(normalize_answer(&question("single_choice"), &json!("Hotel")).unwrap(), json!("Hotel"));
+        assert!(normalize_answer(&question("single_choice"), &json!("Airport")).is_err());
+        assert_eq!(
+            normalize_answer(&question("multi_choice"), &json!(["Hotel", "Hotel", "Downtown"])).unwrap(),
+            json!(["Hotel", "Downtown"])
+        );
+        assert_eq!(normalize_answer(&question("text"), &json!("  ")).unwrap(), json!(null));
+        assert_eq!(normalize_answer(&question("number"), &json!(2)).unwrap(), json!(2));
+    }
+
+    #[test]
+    fn only_the_recipient_of_a_permanent_bounce_is_suppressed() {
+        let bounce = |kind: &str, to: &[&str], message: &str| json!({
+            "to": to,
+            "bounce": { "type": kind, "message": message },
+        });
+
+        assert_eq!(permanent_bounce_address(&bounce("Permanent", &["a@example.com"], "")), Some("a@example.com"));
+        assert_eq!(permanent_bounce_address(&bounce("Transient", &["a@example.com"], "")), None);
+        assert_eq!(
+            permanent_bounce_address(&bounce(
+                "Permanent",
+                &["a@example.com", "B@example.com"],
+                "550 5.1.1 <b@example.com>: mailbox does not exist",
+            )),
+            Some("B@example.com"),
+        );
+        assert_eq!(
+            permanent_bounce_address(&bounce("Permanent", &["a@example.com", "b@example.com"], "Mailbox full")),
+            None,
+        );
+    }
+
+    #[test]
+    fn required_questions_count_stored_answers_when_left_out() {
+        let required = RsvpQuestion {
+            id: Uuid::new_v4(),
+            prompt: "Need a shuttle?".to_string(),
+            question_type: "yes_no".to_string(),
+            scope: "guest".to_string(),
+            required: true,
+            choices: Vec::new(),
+            display_order: 0,
+            active: true,
+            created_at: None,
+        };
+        let questions = [required.clone()];
+        let stored = [QuestionAnswer { question_id: required.id, value: json!(false) }];
+
+        assert!(normalize_answers(&questions, "guest", &mut [], &[], true).is_err());
+        assert!(normalize_answers(&questions, "guest", &mut [], &[], false).is_ok());
+        assert!(normalize_answers(&questions, "guest", &mut [], &stored, true).is_ok());
+        // Clearing a stored answer leaves the question unanswered
+        let mut cleared = [QuestionAnswer { question_id: required.id, value: json!(null) }];
+        assert!(normalize_answers(&questions, "guest", &mut cleared, &stored, true).is_err());
+    }
+}
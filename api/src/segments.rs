@@ -0,0 +1,153 @@
+// Campaign audiences: which guests a campaign is sent to
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::email::is_valid_email;
+use crate::models::{
+    AudienceCount, EmailCampaign, Guest, Invite, InviteWithGuests, SegmentFilters,
+    SEGMENT_RSVP_STATUSES,
+};
+
+/// Lowercase, trim and drop empty values so filters compare case-insensitively.
+fn normalized(values: &[String]) -> Vec<String> {
+    values.iter()
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Check filter values and return them normalized.
+pub fn validate_filters(filters: &SegmentFilters) -> Option<SegmentFilters> {
+    let rsvp_status = normalized(&filters.rsvp_status);
+    if !rsvp_status.iter().all(|s| SEGMENT_RSVP_STATUSES.contains(&s.as_str())) {
+        return None;
+    }
+    Some(SegmentFilters {
+        rsvp_status,
+        sides: normalized(&filters.sides),
+        relationships: normalized(&filters.relationships),
+        tags: normalized(&filters.tags),
+        ..filters.clone()
+    })
+}
+
+/// Non-removed guests on an invite who match the filters. When
+/// `exclude_campaign` is set, invites that already received it are skipped.
+pub async fn matching_guests(
+    db: &PgPool,
+    filters: &SegmentFilters,
+    exclude_campaign: Option<Uuid>,
+) -> Result<Vec<Guest>, sqlx::Error> {
+    sqlx::query_as::<_, Guest>(
+        "SELECT g.* FROM guests g
+         INNER JOIN invites i ON i.id = g.invite_id
+         LEFT JOIN rsvps r ON r.guest_id = g.id
+         WHERE g.removed = false
+           AND (cardinality($1::text[]) = 0 OR
+                CASE WHEN r.id IS NULL THEN 'pending'
+                     WHEN r.attending THEN 'attending'
+                     ELSE 'declined' END = ANY($1))
+           AND (cardinality($2::text[]) = 0 OR LOWER(g.sam_or_jonah) = ANY($2))
+           AND (cardinality($3::text[]) = 0 OR LOWER(g.relationship) = ANY($3))
+           AND (cardinality($4::text[]) = 0 OR g.tags && $4)
+           AND ($5::boolean IS NULL OR $5 = EXISTS (
+                SELECT 1 FROM email_sends es
+                WHERE es.invite_id = i.id AND es.opened_at IS NOT NULL
+                  AND ($6::uuid IS NULL OR es.campaign_id = $6)))
+           AND ($7::boolean IS NULL OR $7 = (i.invite_sent_at IS NOT NULL))
+           AND ($8::uuid IS NULL OR NOT EXISTS (
+                SELECT 1 FROM email_sends es
+                WHERE es.invite_id = i.id AND es.campaign_id = $8))
+         ORDER BY i.created_at, i.id, g.name"
+    )
+    .bind(normalized(&filters.rsvp_status))
+    .bind(normalized(&filters.sides))
+    .bind(normalized(&filters.relationships))
+    .bind(normalized(&filters.tags))
+    .bind(filters.opened)
+    .bind(filters.opened_campaign_id)
+    .bind(filters.invite_sent)
+    .bind(exclude_campaign)
+    .fetch_all(db)
+    .await
+}
+
+/// Matching guests grouped by invite, in invite order.
+pub async fn matching_invites(
+    db: &PgPool,
+    filters: &SegmentFilters,
+    exclude_campaign: Option<Uuid>,
+) -> Result<Vec<InviteWithGuests>, sqlx::Error> {
+    let guests = matching_guests(db, filters, exclude_campaign).await?;
+    let mut invite_ids: Vec<Uuid> = guests.iter().filter_map(|g| g.invite_id).collect();
+    invite_ids.dedup();
+
+    let invites = sqlx::query_as::<_, Invite>("SELECT * FROM invites WHERE id = ANY($1)")
+        .bind(&invite_ids)
+        .fetch_all(db)
+        .await?;
+
+    Ok(invite_ids.into_iter()
+        .filter_map(|id| invites.iter().find(|i| i.id == id).cloned())
+        .map(|invite| InviteWithGuests {
+            guests: guests.iter().filter(|g| g.invite_id == Some(invite.id)).cloned().collect(),
+            invite,
+        })
+        .collect())
+}
+
+/// The filters a campaign is sent with: its segment's, or everyone.
+pub async fn campaign_filters(db: &PgPool, campaign: &EmailCampaign) -> Result<SegmentFilters, sqlx::Error> {
+    let Some(segment_id) = campaign.segment_id else {
+        return Ok(SegmentFilters::default());
+    };
+    let filters = sqlx::query_scalar::<_, sqlx::types::Json<SegmentFilters>>(
+        "SELECT filters FROM audience_segments WHERE id = $1"
+    )
+    .bind(segment_id)
+    .fetch_optional(db)
+    .await?;
+    Ok(filters.map(|f| f.0).unwrap_or_default())
+}
+
+/// Invites still to receive a campaign, with only the guests it targets.
+pub async fn campaign_audience(db: &PgPool, campaign: &EmailCampaign) -> Result<Vec<InviteWithGuests>, sqlx::Error> {
+    let filters = campaign_filters(db, campaign).await?;
+    matching_invites(db, &filters, Some(campaign.id)).await
+}
+
+pub fn count(invites: &[InviteWithGuests]) -> AudienceCount {
+    let guests = invites.iter().flat_map(|i| &i.guests);
+    AudienceCount {
+        invites: invites.len(),
+        guests: guests.clone().count(),
+        recipients: guests.filter(|g| is_valid_email(&g.email)).count(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_filters;
+    use crate::models::SegmentFilters;
+
+    #[test]
+    fn filters_are_normalized_and_statuses_checked() {
+        let filters = SegmentFilters {
+            rsvp_status: vec!["Pending".to_string()],
+            sides: vec![" Sam ".to_string(), "".to_string()],
+            tags: vec!["Out-Of-Town".to_string()],
+            invite_sent: Some(true),
+            ..SegmentFilters::default()
+        };
+
+        let normalized = validate_filters(&filters).unwrap();
+        assert_eq!(normalized.rsvp_status, ["pending"]);
+        assert_eq!(normalized.sides, ["sam"]);
+        assert_eq!(normalized.tags, ["out-of-town"]);
+        assert_eq!(normalized.invite_sent, Some(true));
+
+        let unknown = SegmentFilters { rsvp_status: vec!["maybe".to_string()], ..SegmentFilters::default() };
+        assert!(validate_filters(&unknown).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+// Parsing free-text song requests into artist/title entries
+
+/// A single requested song as typed by a guest
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSong {
+    pub artist: Option<String>,
+    pub title: String,
+}
+
+impl ParsedSong {
+    /// Key used to merge the same song requested by different guests,
+    /// ignoring case, punctuation and spacing.
+    pub fn key(&self) -> String {
+        format!("{}|{}", normalize(&self.title), self.artist.as_deref().map(normalize).unwrap_or_default())
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .replace(['\'', '’'], "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn clean(text: &str) -> String {
+    text.trim()
+        .trim_matches(|c: char| c == '"' || c == '\'' || c == '“' || c == '”' || c == '.' || c == '!')
+        .trim()
+        .to_string()
+}
+
+/// Split a guest's answer into songs. Requests are separated by new lines or
+/// semicolons (commas are common inside names like "Earth, Wind & Fire"),
+/// and each is read as "Title by Artist", "Artist - Title" or just a title.
+pub fn parse_song_requests(text: &str) -> Vec<ParsedSong> {
+    text.split(['\n', ';'])
+        .map(clean)
+        .filter(|entry| !entry.is_empty() && normalize(entry) != "none")
+        .map(|entry| {
+            let lower = entry.to_ascii_lowercase();
+            if let Some(at) = lower.rfind(" by ") {
+                let (title, artist) = (clean(&entry[..at]), clean(&entry[at + 4..]));
+                if !title.is_empty() && !artist.is_empty() {
+                    return ParsedSong { artist: Some(artist), title };
+                }
+            }
+            for separator in [" - ", " – ", " — "] {
+                if let Some((artist, title)) = entry.split_once(separator) {
+                    let (artist, title) = (clean(artist), clean(title));
+                    if !title.is_empty() && !artist.is_empty() {
+                        return ParsedSong { artist: Some(artist), title };
+                    }
+                }
+            }
+            ParsedSong { artist: None, title: entry }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_song_requests, ParsedSong};
+
+    fn song(artist: Option<&str>, title: &str) -> ParsedSong {
+        ParsedSong { artist: artist.map(str::to_string), title: title.to_string() }
+    }
+
+    #[test]
+    fn parses_common_request_formats() {
+        let songs = parse_song_requests("\"September\" by Earth, Wind & Fire\nQueen - Don't Stop Me Now; Shout!\nnone");
+
+        assert_eq!(songs, [
+            song(Some("Earth, Wind & Fire"), "September"),
+            song(Some("Queen"), "Don't Stop Me Now"),
+            song(None, "Shout"),
+        ]);
+    }
+
+    #[test]
+    fn key_ignores_case_and_punctuation() {
+        assert_eq!(
+            song(Some("Queen"), "Don't Stop Me Now").key(),
+            song(Some("QUEEN"), "dont stop me now").key(),
+        );
+        assert_eq!(song(None, "Shout!").key(), song(None, "shout").key());
+    }
+}
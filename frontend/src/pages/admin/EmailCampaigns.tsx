@@ -32,6 +32,18 @@ interface InviteWithGuests {
   guests: Guest[];
 }
 
+interface CampaignPreview {
+  campaign_id: string;
+  template_type: string;
+  invite_id: string;
+  invite_code: string;
+  recipients: string[];
+  subject: string;
+  html: string;
+  text: string;
+  empty_placeholders: string[];
+}
+
 interface RecipientStatus {
   invite: InviteWithGuests;
   sent_at: string | null;
@@ -60,6 +72,17 @@ export default function EmailCampaigns() {
     },
   });
 
+  // Fetch campaign preview
+  const { data: preview, isError: previewFailed } = useQuery<CampaignPreview>({
+    queryKey: ['campaign-preview', previewingCampaignId],
+    queryFn: async () => {
+      const response = await fetch(`${apiUrl}/api/admin/campaigns/${previewingCampaignId}/preview`);
+      if (!response.ok) throw new Error('Failed to fetch preview');
+      return response.json();
+    },
+    enabled: !!previewingCampaignId,
+  });
+
   // Fetch campaign stats
   const { data: stats } = useQuery<CampaignStats>({
     queryKey: ['campaign-stats', viewingCampaignId],
@@ -300,12 +323,42 @@ export default function EmailCampaigns() {
               <h3 className="text-2xl font-display font-bold text-primary">Email Preview</h3>
             </div>
 
-            <div className="p-6">
-              <iframe
-                src={`${apiUrl}/api/admin/campaigns/${previewingCampaignId}/preview`}
-                className="w-full h-[600px] border border-gray-200 rounded-lg"
-                title="Email Preview"
-              />
+            <div className="p-6 space-y-4">
+              {previewFailed ? (
+                <div className="text-center py-12 text-red-600">Failed to load preview</div>
+              ) : !preview ? (
+                <div className="text-center py-12 text-gray-600">Loading preview...</div>
+              ) : (
+                <>
+                  <div>
+                    <div className="text-sm text-gray-600">
+                      Subject (rendered for invite {preview.invite_code})
+                    </div>
+                    <div className="font-semibold text-gray-800">{preview.subject}</div>
+                  </div>
+
+                  {preview.empty_placeholders.length > 0 && (
+                    <div className="p-4 bg-yellow-50 border border-yellow-200 rounded-lg text-sm text-yellow-800">
+                      These placeholders are empty for this invite:{' '}
+                      {preview.empty_placeholders.join(', ')}
+                    </div>
+                  )}
+
+                  <iframe
+                    srcDoc={preview.html}
+                    sandbox=""
+                    className="w-full h-[600px] border border-gray-200 rounded-lg"
+                    title="Email Preview"
+                  />
+
+                  <div>
+                    <div className="text-sm text-gray-600 mb-1">Plain text</div>
+                    <pre className="p-4 bg-gray-50 border border-gray-200 rounded-lg text-sm whitespace-pre-wrap">
+                      {preview.text}
+                    </pre>
+                  </div>
+                </>
+              )}
             </div>
 
             <div className="p-6 border-t border-gray-200 flex justify-end">
@@ -40,6 +40,7 @@ interface AdminRsvpEntryRaw {
   };
   rsvps: RsvpWithGuest[];
   status: string;
+  changed_mind_at: string | null;
 }
 
 interface AdminRsvpEntry {
@@ -49,6 +50,7 @@ interface AdminRsvpEntry {
   guests: Guest[];
   rsvps: RsvpWithGuest[];
   status: string;
+  changed_mind_at: string | null;
 }
 
 type FilterType = 'all' | 'attending' | 'declined' | 'pending' | 'partial';
@@ -79,6 +81,7 @@ export default function RsvpManagement() {
         guests: entry.invite.guests || [],
         rsvps: entry.rsvps || [],
         status: entry.status,
+        changed_mind_at: entry.changed_mind_at,
       }));
     },
   });
@@ -281,6 +284,14 @@ export default function RsvpManagement() {
                     </td>
                     <td className="px-6 py-4 whitespace-nowrap">
                       {statusBadge(entry.status)}
+                      {entry.changed_mind_at && (
+                        <span
+                          className="ml-2 px-2 py-1 rounded-full text-xs font-semibold bg-orange-100 text-orange-800"
+                          title={`Attendance changed ${new Date(entry.changed_mind_at).toLocaleDateString()}`}
+                        >
+                          ↺ Changed
+                        </span>
+                      )}
                     </td>
                     <td className="px-6 py-4">
                       <div className="space-y-1">